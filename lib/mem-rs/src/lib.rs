@@ -0,0 +1,30 @@
+// This file is part of the mem-rs distribution (https://github.com/FrankvdStam/mem-rs).
+// Copyright (c) 2022 Frank van der Stam.
+// https://github.com/FrankvdStam/mem-rs/blob/main/LICENSE
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+mod process_data;
+mod process_module;
+pub mod memory;
+pub mod helpers;
+pub mod process;
+pub mod pointer;
+
+pub mod prelude
+{
+	pub use crate::memory::*;
+	pub use crate::process::*;
+	pub use crate::process_module::*;
+	pub use crate::pointer::*;
+}
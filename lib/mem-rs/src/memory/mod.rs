@@ -0,0 +1,7 @@
+mod base_read_write;
+mod read_write;
+mod memory_type;
+
+pub use base_read_write::*;
+pub use read_write::*;
+pub use memory_type::*;
\ No newline at end of file
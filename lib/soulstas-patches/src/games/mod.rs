@@ -27,3 +27,53 @@ mod x86;
 
 #[cfg(target_arch = "x86")]
 pub use x86::*;
+
+// Signatures shared with the TAS tool, so both scan for the same functions.
+// A new game version usually only needs changes in lib/soulstas-shared/src/signatures.toml.
+pub use soulstas_shared::game_type;
+
+use std::sync::OnceLock;
+
+use log::error;
+use mem_rs::prelude::*;
+use soulstas_shared::signatures::*;
+
+use crate::games::game_type::GameType;
+use crate::util::GLOBAL_VERSION;
+
+// Parsed on the first scan, the DLL only ever runs in one game
+static SIGNATURES: OnceLock<Result<Vec<Signature>, String>> = OnceLock::new();
+
+// AoB scan for a function to patch, using the signature for the running game version.
+// A missing signature or failed scan only skips that patch, a panic would take the game down.
+#[allow(static_mut_refs)]
+pub unsafe fn scan_patch(process: &Process, game_type: GameType, name: &str) -> Option<usize> {
+    let signatures =
+        SIGNATURES.get_or_init(|| load_signatures(SIGNATURES_TOML, game_type, &GLOBAL_VERSION));
+    let signatures = match signatures {
+        Ok(x) => x,
+        Err(err) => {
+            error!("Invalid signatures.toml, skipping {}: {}", name, err);
+            return None;
+        }
+    };
+
+    let signature = match find_signature(signatures, name) {
+        Some(x) => x,
+        None => {
+            error!(
+                "No {} signature for version {}, skipping the patch",
+                name, GLOBAL_VERSION
+            );
+            return None;
+        }
+    };
+
+    match process.scan_abs(name, &signature.pattern, signature.scan_offset, Vec::new()) {
+        Ok(x) => Some(x.get_base_address()),
+        Err(err) => {
+            error!("{}, skipping the patch", err);
+            None
+        }
+    }
+}
@@ -21,6 +21,8 @@ use mem_rs::prelude::*;
 
 use log::info;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
@@ -37,6 +39,23 @@ pub static mut AC6_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut AC6_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut AC6_FRAME_ADVANCE_HOOKED: bool = false;
+
+#[unsafe(no_mangle)]
+#[used]
+pub static mut AC6_FPS_HOOKED: bool = false;
+
+#[unsafe(no_mangle)]
+#[used]
+pub static mut AC6_FPS_HISTORY_HOOKED: bool = false;
+
+#[unsafe(no_mangle)]
+#[used]
+pub static mut AC6_FPS_CUSTOM_LIMIT_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut AC6_FPS_PATCH_ENABLED: bool = false;
@@ -54,104 +73,86 @@ pub fn init_armoredcore6() {
         process.refresh().unwrap();
 
         // AoB scan for frame advance patch
-        let fn_frame_advance_address = process
-            .scan_abs(
-                "frame_advance",
-                "e8 ? ? ? ? 84 c0 74 4b 0f 1f 00",
-                12,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::ArmoredCore6, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            AC6_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // AoB scan for FPS patch
-        let fn_fps_address = process
-            .scan_abs(
-                "fps",
-                "8b 83 64 02 00 00 89 83 d4 02 00 00 f3 0f 58 93 64 02 00 00",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("FPS at 0x{:x}", fn_fps_address);
-
-        // Enable FPS patch
-        FPS_HOOK = Some(
-            Hooker::new(
-                fn_fps_address,
-                HookType::JmpBack(fps),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_fps_address) = scan_patch(&process, GameType::ArmoredCore6, "fps") {
+            info!("FPS at 0x{:x}", fn_fps_address);
+
+            // Enable FPS patch
+            FPS_HOOK = Some(
+                Hooker::new(
+                    fn_fps_address,
+                    HookType::JmpBack(fps),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            AC6_FPS_HOOKED = true;
+        }
 
         // AoB scan for FPS history patch
-        let fn_fps_history_address = process
-            .scan_abs(
-                "fps history",
-                "48 89 01 0f b6 83 94 02 00 00 89 41 08",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("FPS history at 0x{:x}", fn_fps_history_address);
-
-        // Enable FPS history patch
-        FPS_HISTORY_HOOK = Some(
-            Hooker::new(
-                fn_fps_history_address,
-                HookType::JmpBack(fps_history),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_fps_history_address) =
+            scan_patch(&process, GameType::ArmoredCore6, "fps history")
+        {
+            info!("FPS history at 0x{:x}", fn_fps_history_address);
+
+            // Enable FPS history patch
+            FPS_HISTORY_HOOK = Some(
+                Hooker::new(
+                    fn_fps_history_address,
+                    HookType::JmpBack(fps_history),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            AC6_FPS_HISTORY_HOOKED = true;
+        }
 
         // AoB scan for FPS custom limit patch
-        let fn_fps_custom_limit_address = process
-            .scan_abs(
-                "fps custom limit",
-                "45 0f 57 d2 44 38 a3 ed 02 00 00",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("FPS custom limit at 0x{:x}", fn_fps_custom_limit_address);
-
-        // Enable FPS custom limit patch
-        FPS_CUSTOM_LIMIT_HOOK = Some(
-            Hooker::new(
-                fn_fps_custom_limit_address,
-                HookType::JmpBack(fps_custom_limit),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_fps_custom_limit_address) =
+            scan_patch(&process, GameType::ArmoredCore6, "fps custom limit")
+        {
+            info!("FPS custom limit at 0x{:x}", fn_fps_custom_limit_address);
+
+            // Enable FPS custom limit patch
+            FPS_CUSTOM_LIMIT_HOOK = Some(
+                Hooker::new(
+                    fn_fps_custom_limit_address,
+                    HookType::JmpBack(fps_custom_limit),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            AC6_FPS_CUSTOM_LIMIT_HOOKED = true;
+        }
     }
 }
 
@@ -23,6 +23,8 @@ use log::info;
 
 use windows::Win32::UI::Input::XboxController::*;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
@@ -36,6 +38,11 @@ pub static mut DS1R_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut DS1R_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS1R_FRAME_ADVANCE_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut DS1R_XINPUT_PATCH_ENABLED: bool = false;
@@ -71,29 +78,25 @@ pub fn init_darksouls1remastered() {
         // AoB scan for frame advance patch
         // Hooked in a slightly different spot than the other games, to "hide" from anti-code-patch
         // Old/Bad AoB: "e8 ? ? ? ? 84 c0 74 08 48 83 7c 24 38 00 75 ef"
-        let fn_frame_advance_address = process
-            .scan_abs(
-                "frame_advance",
-                "e8 ? ? ? ? 48 8b c8 e8 ? ? ? ? e8 ? ? ? ? f3 0f 10 35 ? ? ? ?",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::DarkSouls1Remastered, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            DS1R_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // Find XInputGetState function in XINPUT1_3.dll
         let xinput_module = process
@@ -24,6 +24,8 @@ use log::info;
 use windows::Win32::System::Performance::*;
 use windows::Win32::UI::Input::XboxController::*;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
@@ -38,6 +40,15 @@ pub static mut DS2SOTFS_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut DS2SOTFS_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS2SOTFS_FRAME_ADVANCE_HOOKED: bool = false;
+
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS2SOTFS_FPS_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut DS2SOTFS_FPS_PATCH_ENABLED: bool = false;
@@ -108,49 +119,47 @@ pub fn init_darksouls2sotfs() {
         let mut process = Process::new_with_memory_type("DarkSoulsII.exe", MemoryType::Direct);
         process.refresh().unwrap();
 
-        let fn_frame_advance_address = process.scan_abs("frame_advance", "80 b9 36 01 00 00 00 48 8b d9 74 16 48 8b 49 08 ba 01 00 00 00 ff ? ? ? ? ? c6 83 36 01 00 00 00 48 8b cb", 0, Vec::new()).unwrap().get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::DarkSouls2Sotfs, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            DS2SOTFS_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // Get performance frequency
         let _ = QueryPerformanceFrequency(&mut PERFORMANCE_FREQUENCY);
 
         // AoB scan for FPS patch
-        let fn_fps_address = process
-            .scan_abs(
-                "fps",
-                "48 8b c4 56 57 41 56 48 81 ec 90 00 00 00 0f 29 70 c8",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("FPS at 0x{:x}", fn_fps_address);
-
-        // Enable FPS patch
-        FPS_HOOK = Some(
-            Hooker::new(
-                fn_fps_address,
-                HookType::Retn(fps),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_fps_address) = scan_patch(&process, GameType::DarkSouls2Sotfs, "fps") {
+            info!("FPS at 0x{:x}", fn_fps_address);
+
+            // Enable FPS patch
+            FPS_HOOK = Some(
+                Hooker::new(
+                    fn_fps_address,
+                    HookType::Retn(fps),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            DS2SOTFS_FPS_HOOKED = true;
+        }
 
         // Find XInputGetState function in XINPUT1_3.dll
         let xinput_module = process
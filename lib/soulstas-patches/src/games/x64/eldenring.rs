@@ -23,8 +23,9 @@ use log::info;
 
 use windows::Win32::UI::Input::XboxController::*;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
-use crate::util::Version;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
 static mut XINPUT_HOOK: Option<HookPoint> = None;
@@ -37,6 +38,11 @@ pub static mut ER_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut ER_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut ER_FRAME_ADVANCE_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut ER_XINPUT_PATCH_ENABLED: bool = false;
@@ -69,50 +75,25 @@ pub fn init_eldenring() {
         process.refresh().unwrap();
 
         // AoB scan for frame advance patch
-        let fn_frame_advance_address = if (GLOBAL_VERSION
-            >= Version {
-                major: 1,
-                minor: 2,
-                build: 2,
-                revision: 0,
-            })
-        // v1.02.2+
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::EldenRing, "frame_advance")
         {
-            process
-                .scan_abs(
-                    "frame_advance",
-                    "e8 ? ? ? ? e8 ? ? ? ? 84 c0 74 4f",
-                    21,
-                    Vec::new(),
-                )
-                .unwrap()
-                .get_base_address()
-        } else {
-            process
-                .scan_abs(
-                    "frame_advance",
-                    "e8 ? ? ? ? e8 ? ? ? ? 84 c0 74 48 48 8b 0d ? ? ? ?",
-                    14,
-                    Vec::new(),
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
                 )
-                .unwrap()
-                .get_base_address()
-        };
-
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+                .hook()
+                .unwrap(),
+            );
+            ER_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // Find XInputGetState function in XINPUT1_4.dll
         let xinput_module = process
@@ -4,6 +4,8 @@ use mem_rs::prelude::*;
 use std::thread;
 use std::time::Duration;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
@@ -12,6 +14,11 @@ static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
 #[used]
 pub static mut NR_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut NR_FRAME_ADVANCE_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut NR_FRAME_ADVANCE_ENABLED: bool = false;
@@ -23,29 +30,25 @@ pub fn init_nightreign() {
         process.refresh().unwrap();
 
         // AoB scan for frame advance patch
-        let fn_frame_advance_address = process
-            .scan_abs(
-                "frame_advance",
-                "e8 ? ? ? ? e8 ? ? ? ? 84 c0 74 4f",
-                21,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::NightReign, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            NR_FRAME_ADVANCE_HOOKED = true;
+        }
     }
 }
 
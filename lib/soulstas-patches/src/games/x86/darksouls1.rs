@@ -23,6 +23,8 @@ use log::info;
 
 use windows::Win32::UI::Input::XboxController::*;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
@@ -36,6 +38,11 @@ pub static mut DS1_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut DS1_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS1_FRAME_ADVANCE_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut DS1_XINPUT_PATCH_ENABLED: bool = false;
@@ -69,29 +76,25 @@ pub fn init_darksouls1() {
         process.refresh().unwrap();
 
         // AoB scan for frame advance patch
-        let fn_frame_advance_address = process
-            .scan_abs(
-                "frame_advance",
-                "e8 ? ? ? ? 84 c0 74 07 83 7c 24",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::DarkSouls1, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            DS1_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // Find XInputGetState function in XINPUT1_3.dll
         let xinput_module = process
@@ -25,8 +25,9 @@ use log::info;
 use windows::Win32::System::Performance::*;
 use windows::Win32::UI::Input::XboxController::*;
 
+use crate::games::game_type::GameType;
+use crate::games::scan_patch;
 use crate::util::GLOBAL_VERSION;
-use crate::util::Version;
 
 static mut FRAME_ADVANCE_HOOK: Option<HookPoint> = None;
 static mut FPS_HOOK: Option<HookPoint> = None;
@@ -40,6 +41,15 @@ pub static mut DS2_FRAME_ADVANCE_ENABLED: bool = false;
 #[used]
 pub static mut DS2_FRAME_RUNNING: bool = false;
 
+// Set once a patch is in, a failed scan skips it and the TAS tool has to know
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS2_FRAME_ADVANCE_HOOKED: bool = false;
+
+#[unsafe(no_mangle)]
+#[used]
+pub static mut DS2_FPS_HOOKED: bool = false;
+
 #[unsafe(no_mangle)]
 #[used]
 pub static mut DS2_FPS_PATCH_ENABLED: bool = false;
@@ -113,76 +123,48 @@ pub fn init_darksouls2() {
         process.refresh().unwrap();
 
         // AoB scan for frame advance patch
-        let fn_frame_advance_address = process
-            .scan_abs(
-                "frame_advance",
-                "8b ce e8 ? ? ? ? 80 be e2 00 00 00 00 74 17 8b 16",
-                0,
-                Vec::new(),
-            )
-            .unwrap()
-            .get_base_address();
-        info!("Frame advance at 0x{:x}", fn_frame_advance_address);
-
-        // Enable frame advance patch
-        FRAME_ADVANCE_HOOK = Some(
-            Hooker::new(
-                fn_frame_advance_address,
-                HookType::JmpBack(frame_advance),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+        if let Some(fn_frame_advance_address) =
+            scan_patch(&process, GameType::DarkSouls2, "frame_advance")
+        {
+            info!("Frame advance at 0x{:x}", fn_frame_advance_address);
+
+            // Enable frame advance patch
+            FRAME_ADVANCE_HOOK = Some(
+                Hooker::new(
+                    fn_frame_advance_address,
+                    HookType::JmpBack(frame_advance),
+                    CallbackOption::None,
+                    0,
+                    HookFlags::empty(),
+                )
+                .hook()
+                .unwrap(),
+            );
+            DS2_FRAME_ADVANCE_HOOKED = true;
+        }
 
         // Get performance frequency
         let _ = QueryPerformanceFrequency(&mut PERFORMANCE_FREQUENCY);
 
         // AoB scan for FPS patch
         // (different AOB on first release)
-        let fn_fps_address = if (GLOBAL_VERSION
-            > Version {
-                major: 0,
-                minor: 0,
-                build: 0,
-                revision: 0,
-            }) {
-            process
-                .scan_abs(
-                    "fps",
-                    "55 8b ec 83 ec 3c 53 56 8b f1 8b 46 24",
+        if let Some(fn_fps_address) = scan_patch(&process, GameType::DarkSouls2, "fps") {
+            info!("FPS at 0x{:x}", fn_fps_address);
+
+            // Enable FPS patch
+            FPS_HOOK = Some(
+                Hooker::new(
+                    fn_fps_address,
+                    HookType::Retn(4, fps),
+                    CallbackOption::None,
                     0,
-                    Vec::new(),
+                    HookFlags::empty(),
                 )
-                .unwrap()
-                .get_base_address()
-        } else {
-            process
-                .scan_abs(
-                    "fps",
-                    "55 8b ec 83 ec 3c 53 56 57 8b f1 8b 5e 20 8b 7e 24 8d 45 e4 50",
-                    0,
-                    Vec::new(),
-                )
-                .unwrap()
-                .get_base_address()
-        };
-        info!("FPS at 0x{:x}", fn_fps_address);
-
-        // Enable FPS patch
-        FPS_HOOK = Some(
-            Hooker::new(
-                fn_fps_address,
-                HookType::Retn(4, fps),
-                CallbackOption::None,
-                0,
-                HookFlags::empty(),
-            )
-            .hook()
-            .unwrap(),
-        );
+                .hook()
+                .unwrap(),
+            );
+            DS2_FPS_HOOKED = true;
+        }
 
         // Find XInputGetState function in XINPUT1_3.dll
         let xinput_module = process
@@ -15,7 +15,6 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 mod globals;
-mod version;
 
 pub use globals::*;
-pub use version::*;
+pub use soulstas_shared::version::*;
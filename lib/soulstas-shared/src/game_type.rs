@@ -0,0 +1,39 @@
+// Everything else about the games is in the registry of the TAS tool
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameType {
+    DarkSouls1,
+    DarkSouls1Remastered,
+    DarkSouls2,
+    DarkSouls2Sotfs,
+    DarkSouls3,
+    Sekiro,
+    EldenRing,
+    ArmoredCore6,
+    NightReign,
+}
+
+pub const GAME_TYPES: &[GameType] = &[
+    GameType::DarkSouls1,
+    GameType::DarkSouls1Remastered,
+    GameType::DarkSouls2,
+    GameType::DarkSouls2Sotfs,
+    GameType::DarkSouls3,
+    GameType::Sekiro,
+    GameType::EldenRing,
+    GameType::ArmoredCore6,
+    GameType::NightReign,
+];
+
+// Lowercase name, used for the tables in signatures.toml and by soulstas-sigcheck
+pub fn game_type_to_string(game_type: GameType) -> String {
+    return format!("{:?}", game_type).to_lowercase();
+}
+
+pub fn string_to_game_type(name: &str) -> Option<GameType> {
+    let name = name.to_lowercase();
+    return GAME_TYPES
+        .iter()
+        .find(|x| game_type_to_string(**x) == name)
+        .copied();
+}
@@ -0,0 +1,6 @@
+// Used by the TAS tool, soulstas-patches and soulstas-sigcheck, so all of them scan for the
+// same signatures. The Windows parts stay out, except for reading a game's version.
+
+pub mod game_type;
+pub mod signatures;
+pub mod version;
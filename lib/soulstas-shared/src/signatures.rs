@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::game_type::*;
+use crate::version::*;
+
+// Every AoB signature of every game, both the ones the TAS tool scans for pointers
+// and the ones soulstas-patches scans for the functions it hooks.
+// They live in signatures.toml, so a new game version usually only needs changes there.
+// The game modules look theirs up by name, `info` checks all of them at once.
+
+pub const SIGNATURES_TOML: &str = include_str!("signatures.toml");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureSource {
+    Tool,    // Pointer used by the TAS tool
+    Patches, // Function hooked by soulstas-patches, has to match the scan there
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanType {
+    Absolute,
+    Relative { instruction_size: usize }, // x64, the address is relative to the end of the instruction
+}
+
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub name: String,
+    pub source: SignatureSource,
+    pub pattern: String,
+    pub scan_type: ScanType,
+    pub scan_offset: usize,
+    pub offsets: Vec<usize>, // Pointer chain after the scan, the last one isn't followed
+}
+
+// [game] table in signatures.toml, unknown keys are errors so typos don't get ignored silently
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameTable {
+    #[serde(default)]
+    offset: Vec<OffsetEntry>,
+    #[serde(default)]
+    signature: Vec<SignatureEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OffsetEntry {
+    name: String,
+    value: usize,
+    min_version: Option<String>,
+    max_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignatureEntry {
+    name: String,
+    pattern: String,
+    scan: ScanName,
+    scan_offset: usize,
+    instruction_size: Option<usize>,
+    #[serde(default)]
+    offsets: Vec<OffsetValue>,
+    source: Option<SignatureSource>,
+    min_version: Option<String>,
+    max_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ScanName {
+    Abs,
+    Rel,
+}
+
+// Either a number or a named offset
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OffsetValue {
+    Value(usize),
+    Named(String),
+}
+
+// Signatures for a version of a game, some offsets changed between versions
+pub fn game_signatures(game_type: GameType, version: &Version) -> Vec<Signature> {
+    // Built in, so a mistake in it is a bug and not something to recover from
+    return load_signatures(SIGNATURES_TOML, game_type, version)
+        .unwrap_or_else(|err| panic!("Invalid signatures.toml: {}", err));
+}
+
+pub fn load_signatures(
+    text: &str,
+    game_type: GameType,
+    version: &Version,
+) -> Result<Vec<Signature>, String> {
+    let game = game_type_to_string(game_type);
+    let mut tables: HashMap<String, GameTable> =
+        toml::from_str(text).map_err(|err| err.to_string())?;
+    let table = match tables.remove(&game) {
+        Some(x) => x,
+        None => return Ok(Vec::new()),
+    };
+
+    // Named offsets first, the signatures use them. The first one for the version wins.
+    let mut offsets: Vec<(String, usize)> = Vec::new();
+    for offset in table.offset.iter() {
+        let error = |err: String| format!("[[{}.offset]] {}: {}", game, offset.name, err);
+
+        if version_matches(&offset.min_version, &offset.max_version, version).map_err(error)?
+            && !offsets.iter().any(|x| x.0 == offset.name)
+        {
+            offsets.push((offset.name.clone(), offset.value));
+        }
+    }
+
+    let mut signatures: Vec<Signature> = Vec::new();
+    for entry in table.signature.iter() {
+        let error = |err: String| format!("[[{}.signature]] {}: {}", game, entry.name, err);
+
+        let signature = entry_to_signature(entry, &offsets).map_err(error)?;
+        if version_matches(&entry.min_version, &entry.max_version, version).map_err(error)?
+            && find_signature(&signatures, &signature.name).is_none()
+        {
+            signatures.push(signature);
+        }
+    }
+
+    return Ok(signatures);
+}
+
+pub fn find_signature<'a>(signatures: &'a [Signature], name: &str) -> Option<&'a Signature> {
+    return signatures.iter().find(|x| x.name == name);
+}
+
+fn entry_to_signature(
+    entry: &SignatureEntry,
+    offsets: &[(String, usize)],
+) -> Result<Signature, String> {
+    let scan_type = match (entry.scan, entry.instruction_size) {
+        (ScanName::Abs, None) => ScanType::Absolute,
+        (ScanName::Rel, Some(instruction_size)) => ScanType::Relative {
+            instruction_size: instruction_size,
+        },
+        (ScanName::Abs, Some(_)) => return Err(String::from("instruction_size is only for rel")),
+        (ScanName::Rel, None) => return Err(String::from("instruction_size is missing")),
+    };
+
+    let mut pointer_offsets: Vec<usize> = Vec::new();
+    for offset in entry.offsets.iter() {
+        match offset {
+            OffsetValue::Value(x) => pointer_offsets.push(*x),
+            OffsetValue::Named(x) => pointer_offsets.push(named_offset(x, offsets)?),
+        }
+    }
+
+    return Ok(Signature {
+        name: entry.name.clone(),
+        source: entry.source.unwrap_or(SignatureSource::Tool),
+        pattern: entry.pattern.clone(),
+        scan_type: scan_type,
+        scan_offset: entry.scan_offset,
+        offsets: pointer_offsets,
+    });
+}
+
+// "name" or "name + 0x10"
+fn named_offset(text: &str, offsets: &[(String, usize)]) -> Result<usize, String> {
+    let (name, add) = match text.split_once('+') {
+        Some((name, add)) => (name.trim(), add.trim()),
+        None => (text.trim(), "0"),
+    };
+
+    let value = match offsets.iter().find(|x| x.0 == name) {
+        Some(x) => x.1,
+        None => return Err(format!("Unknown offset {}", name)),
+    };
+    let add = match add.strip_prefix("0x") {
+        Some(x) => usize::from_str_radix(x, 16),
+        None => add.parse::<usize>(),
+    }
+    .map_err(|_| format!("Invalid offset {}", text))?;
+
+    return Ok(value + add);
+}
+
+// min_version and max_version are both inclusive
+fn version_matches(
+    min_version: &Option<String>,
+    max_version: &Option<String>,
+    version: &Version,
+) -> Result<bool, String> {
+    if let Some(text) = min_version {
+        let min_version = string_to_version(text).ok_or("Invalid min_version")?;
+        if *version < min_version {
+            return Ok(false);
+        }
+    }
+    if let Some(text) = max_version {
+        let max_version = string_to_version(text).ok_or("Invalid max_version")?;
+        if *version > max_version {
+            return Ok(false);
+        }
+    }
+
+    return Ok(true);
+}
+
+// Like "1.7.0.0", missing parts are 0
+fn string_to_version(text: &str) -> Option<Version> {
+    let mut parts = [0u16; 4];
+    for (i, part) in text.split('.').enumerate() {
+        *parts.get_mut(i)? = part.trim().parse().ok()?;
+    }
+
+    return Some(Version {
+        major: parts[0],
+        minor: parts[1],
+        build: parts[2],
+        revision: parts[3],
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major: u16, minor: u16) -> Version {
+        return Version {
+            major: major,
+            minor: minor,
+            build: 0,
+            revision: 0,
+        };
+    }
+
+    #[test]
+    fn built_in_signatures_load() {
+        for game_type in GAME_TYPES.iter() {
+            let signatures = load_signatures(SIGNATURES_TOML, *game_type, &version(99, 0));
+            assert!(
+                signatures.is_ok(),
+                "{:?}: {:?}",
+                game_type,
+                signatures.err()
+            );
+        }
+    }
+
+    #[test]
+    fn first_offset_for_the_version_wins() {
+        let text = r#"
+            [[eldenring.offset]]
+            name = "player"
+            value = 0x10
+            max_version = "1.2"
+
+            [[eldenring.offset]]
+            name = "player"
+            value = 0x20
+
+            [[eldenring.signature]]
+            name = "hp"
+            pattern = "48 8b 05"
+            scan = "rel"
+            scan_offset = 3
+            instruction_size = 7
+            offsets = [0, "player + 0x8"]
+        "#;
+
+        let old = load_signatures(text, GameType::EldenRing, &version(1, 2)).unwrap();
+        assert_eq!(old[0].offsets, vec![0, 0x18]);
+        let new = load_signatures(text, GameType::EldenRing, &version(1, 3)).unwrap();
+        assert_eq!(new[0].offsets, vec![0, 0x28]);
+        assert_eq!(
+            new[0].scan_type,
+            ScanType::Relative {
+                instruction_size: 7
+            }
+        );
+        assert_eq!(new[0].source, SignatureSource::Tool);
+    }
+
+    #[test]
+    fn mistakes_are_errors() {
+        let typo = "[[eldenring.signature]]\nname = \"hp\"\npattern = \"48\"\nscan = \"abs\"\nscan_ofset = 3\n";
+        assert!(load_signatures(typo, GameType::EldenRing, &version(1, 0)).is_err());
+
+        let unknown_offset = "[[eldenring.signature]]\nname = \"hp\"\npattern = \"48\"\nscan = \"abs\"\nscan_offset = 3\noffsets = [\"nope\"]\n";
+        assert!(load_signatures(unknown_offset, GameType::EldenRing, &version(1, 0)).is_err());
+
+        let missing_size = "[[eldenring.signature]]\nname = \"hp\"\npattern = \"48\"\nscan = \"rel\"\nscan_offset = 3\n";
+        assert!(load_signatures(missing_size, GameType::EldenRing, &version(1, 0)).is_err());
+    }
+}
@@ -2,14 +2,21 @@
 // Thanks Wasted!
 
 use std::cmp::Ordering;
-use std::ffi::c_void;
 use std::fmt::{Display, Formatter};
+
+// Reading the version is Windows only, soulstas-sigcheck only compares them
+#[cfg(windows)]
+use std::ffi::c_void;
+#[cfg(windows)]
 use std::mem::MaybeUninit;
+#[cfg(windows)]
 use std::path::PathBuf;
+#[cfg(windows)]
 use windows::Win32::Storage::FileSystem::{
     GET_FILE_VERSION_INFO_FLAGS, GetFileVersionInfoExW, GetFileVersionInfoSizeW, VS_FIXEDFILEINFO,
     VerQueryValueW,
 };
+#[cfg(windows)]
 use windows::core::PCWSTR;
 
 pub struct Version {
@@ -40,6 +47,7 @@ impl Display for Version {
     }
 }
 
+#[cfg(windows)]
 impl Version {
     #[allow(dead_code)]
     pub fn from_file_version_info(path: PathBuf) -> Self {
@@ -0,0 +1,602 @@
+use std::path::PathBuf;
+
+use crate::config::*;
+use crate::error::*;
+use crate::games::*;
+use crate::utils::actions::*;
+
+// Command line parsing and help texts, generated from the game and action lists
+
+#[derive(Debug)]
+pub enum Command {
+    Run {
+        game: Option<String>,
+        script: PathBuf,
+        options: AttachOptions,
+        start_frame: u32,
+    },
+    Check {
+        game: Option<String>,
+        script: PathBuf,
+    },
+    Lint {
+        script: PathBuf,
+    },
+    Fmt {
+        script: PathBuf,
+        write: bool,
+    },
+    Record {
+        game: Option<String>,
+        script: PathBuf,
+        output: PathBuf,
+        options: AttachOptions,
+    },
+    Monitor {
+        game: Option<String>,
+        options: AttachOptions,
+    },
+    Info {
+        game: Option<String>,
+    },
+    Help {
+        topic: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AttachOptions {
+    pub pid: Option<u32>, // Which instance to attach to when several are running
+    pub launch: Option<String>, // Executable or Steam app ID
+    pub launch_args: Vec<String>,
+    pub save: Option<PathBuf>, // Overrides `@save` in the script
+    pub save_slot: Option<String>,
+    pub save_dir: Option<PathBuf>, // Overrides the game's save folder
+    pub log_level: Option<LogLevel>,
+}
+
+impl AttachOptions {
+    // Fills in what wasn't given on the command line from the game's soulstas.toml section
+    pub fn with_game_config(&self, game_config: Option<&GameConfig>) -> AttachOptions {
+        let mut options = self.clone();
+        let game_config = match game_config {
+            Some(x) => x,
+            None => return options,
+        };
+
+        // --pid means attaching to a running game, so don't launch one
+        if options.launch.is_none() && options.pid.is_none() {
+            options.launch = game_config.launch.clone();
+            if options.launch_args.is_empty() {
+                options.launch_args = game_config.launch_args.clone();
+            }
+        }
+        options.save_slot = options.save_slot.or(game_config.save_slot.clone());
+        options.save_dir = options.save_dir.or(game_config.save_dir.clone());
+
+        return options;
+    }
+}
+
+const COMMANDS: &[(&str, &str)] = &[
+    (
+        "run [game] (script) [--start-frame (frame)] [--pid (pid)] [--launch (exe/appid)] [--save (path)] [--save-slot (slot)] [--save-dir (path)] [--log-level (level)]",
+        "Run a TAS script",
+    ),
+    (
+        "check [game] (script)",
+        "Check if a script is valid and the game supports all of its actions",
+    ),
+    (
+        "lint (script)",
+        "Look for mistakes in a script, like keys that are never released",
+    ),
+    (
+        "fmt (script) [--write]",
+        "Clean up the formatting of a script, --write changes the file instead of printing it",
+    ),
+    (
+        "record [game] (script) (output) [--pid (pid)] [--launch (exe/appid)] [--save (path)] [--save-slot (slot)] [--save-dir (path)] [--log-level (level)]",
+        "Run a TAS script and write every input that was sent to a file",
+    ),
+    (
+        "monitor [game] [--pid (pid)] [--launch (exe/appid)]",
+        "Show the state of the game live, without running a script",
+    ),
+    (
+        "info [game]",
+        "List supported games, or details about one. If it's running, also check all of its signatures",
+    ),
+    (
+        "help [actions]",
+        "Show this help, or all TAS script actions",
+    ),
+];
+
+pub fn parse_args(args: &[String]) -> Result<Command, SoulsTasError> {
+    if args.is_empty() {
+        return Ok(Command::Help { topic: None });
+    }
+
+    // Split off options, everything else is positional
+    let mut positional: Vec<&str> = Vec::new();
+    let mut options = AttachOptions::default();
+    let mut start_frame: u32 = 0;
+    let mut write = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--pid" => {
+                options.pid = Some(parse_option_value(args, i, "--pid")?);
+                i += 1;
+            }
+            "--start-frame" => {
+                start_frame = parse_option_value(args, i, "--start-frame")?;
+                i += 1;
+            }
+            "--launch" => {
+                options.launch = Some(parse_option_value(args, i, "--launch")?);
+                i += 1;
+            }
+            "--save" => {
+                options.save = Some(parse_option_value(args, i, "--save")?);
+                i += 1;
+            }
+            "--save-slot" => {
+                options.save_slot = Some(parse_option_value(args, i, "--save-slot")?);
+                i += 1;
+            }
+            "--save-dir" => {
+                options.save_dir = Some(parse_option_value(args, i, "--save-dir")?);
+                i += 1;
+            }
+            "--log-level" => {
+                let value: String = parse_option_value(args, i, "--log-level")?;
+                options.log_level = Some(string_to_log_level(&value).ok_or(
+                    SoulsTasError::Usage(format!(
+                        "Invalid log level {}, use error, info or frame.",
+                        value
+                    )),
+                )?);
+                i += 1;
+            }
+            "--" => {
+                // Everything after this is for the launched game
+                options.launch_args = args[i + 1..].to_vec();
+                break;
+            }
+            "--write" => {
+                write = true;
+            }
+            "--help" | "-h" => {
+                return Ok(Command::Help { topic: None });
+            }
+            x if x.starts_with("--") => {
+                return Err(SoulsTasError::Usage(format!("Unknown option {}.", x)));
+            }
+            x => {
+                positional.push(x);
+            }
+        }
+        i += 1;
+    }
+
+    if positional.is_empty() {
+        return Ok(Command::Help { topic: None });
+    }
+
+    if options.launch.is_some() && options.pid.is_some() {
+        return Err(SoulsTasError::Usage(String::from(
+            "--launch and --pid can't be used together.",
+        )));
+    }
+    if options.launch.is_none() && !options.launch_args.is_empty() {
+        return Err(SoulsTasError::Usage(String::from(
+            "Game arguments after -- need --launch.",
+        )));
+    }
+
+    let command = positional[0].to_lowercase();
+    let params = &positional[1..];
+
+    // The old way of running scripts, without a subcommand
+    if string_to_game(&command).is_some() || is_simulation(&command) {
+        check_param_count(params, 1)?;
+        return Ok(Command::Run {
+            game: Some(command),
+            script: PathBuf::from(params[0]),
+            options: options,
+            start_frame: start_frame,
+        });
+    }
+
+    return match command.as_str() {
+        "run" => {
+            let (game, params) = split_game_param(params, 1)?;
+            Ok(Command::Run {
+                game: game,
+                script: PathBuf::from(params[0]),
+                options: options,
+                start_frame: start_frame,
+            })
+        }
+        "check" => {
+            let (game, params) = split_game_param(params, 1)?;
+            Ok(Command::Check {
+                game: game,
+                script: PathBuf::from(params[0]),
+            })
+        }
+        "lint" => {
+            check_param_count(params, 1)?;
+            Ok(Command::Lint {
+                script: PathBuf::from(params[0]),
+            })
+        }
+        "fmt" => {
+            check_param_count(params, 1)?;
+            Ok(Command::Fmt {
+                script: PathBuf::from(params[0]),
+                write: write,
+            })
+        }
+        "record" => {
+            let (game, params) = split_game_param(params, 2)?;
+            Ok(Command::Record {
+                game: game,
+                script: PathBuf::from(params[0]),
+                output: PathBuf::from(params[1]),
+                options: options,
+            })
+        }
+        "monitor" => {
+            let (game, params) = split_game_param(params, 0)?;
+            Ok(Command::Monitor {
+                game: game,
+                options: options,
+            })
+        }
+        "info" => {
+            if params.len() > 1 {
+                return Err(SoulsTasError::Usage(String::from(
+                    "Invalid argument count.",
+                )));
+            }
+            Ok(Command::Info {
+                game: params.first().map(|x| x.to_string()),
+            })
+        }
+        "help" => Ok(Command::Help {
+            topic: params.first().map(|x| x.to_lowercase()),
+        }),
+        _ => Err(SoulsTasError::Usage(format!(
+            "Unknown command {}.",
+            command
+        ))),
+    };
+}
+
+fn parse_option_value<T: std::str::FromStr>(
+    args: &[String],
+    index: usize,
+    name: &str,
+) -> Result<T, SoulsTasError> {
+    return match args.get(index + 1).map(|x| x.parse::<T>()) {
+        Some(Ok(x)) => Ok(x),
+        _ => Err(SoulsTasError::Usage(format!("Invalid value for {}.", name))),
+    };
+}
+
+fn check_param_count(params: &[&str], count: usize) -> Result<(), SoulsTasError> {
+    if params.len() != count {
+        return Err(SoulsTasError::Usage(String::from(
+            "Invalid argument count.",
+        )));
+    }
+
+    return Ok(());
+}
+
+// The game is optional in front of the other arguments, it gets detected if left out
+fn split_game_param<'a>(
+    params: &'a [&'a str],
+    count: usize,
+) -> Result<(Option<String>, &'a [&'a str]), SoulsTasError> {
+    if params.len() == count + 1 {
+        return Ok((Some(params[0].to_string()), &params[1..]));
+    }
+
+    check_param_count(params, count)?;
+    return Ok((None, params));
+}
+
+// The game from soulstas.toml is used when none was given
+pub fn apply_config(command: &mut Command, config: &Config) {
+    match command {
+        Command::Run { game, .. }
+        | Command::Check { game, .. }
+        | Command::Record { game, .. }
+        | Command::Monitor { game, .. } => {
+            if game.is_none() {
+                *game = config.game.clone();
+            }
+        }
+        _ => {}
+    }
+}
+
+// Short reminder after usage errors
+pub fn usage() -> String {
+    return format!(
+        "Usage: {} (command) ..., see `{} help` for all commands.",
+        Architecture::current().executable(),
+        Architecture::current().executable()
+    );
+}
+
+pub fn help_text() -> String {
+    let executable = Architecture::current().executable();
+    let mut text = format!("Usage: {} (command) ...\n\nCommands:\n", executable);
+
+    for (syntax, description) in COMMANDS {
+        text.push_str(&format!("  {}\n      {}\n", syntax, description));
+    }
+
+    text.push_str("\nGames:\n");
+    for game in GAMES {
+        text.push_str(&format!(
+            "  {} ({}, {})\n",
+            game.aliases.join("/"),
+            game.name,
+            game.architecture.executable()
+        ));
+    }
+    text.push_str("  sim (Simulated game, for trying out scripts)\n");
+
+    text.push_str(
+        "\n--launch starts the game first and waits until it's ready, arguments for it go after --.\n",
+    );
+    text.push_str(
+        "--save copies a save file into the game's save folder before launching and restores the original afterwards.\n",
+    );
+    text.push_str(
+        "Without a game, the running one is used, --pid (process id) picks one if several are running.\nA `; game: (name)` comment at the top of a script makes sure it only runs on that game.\n",
+    );
+    text.push_str(
+        "--log-level (error/info/frame) sets how much gets printed while running, frame is the default.\n",
+    );
+    text.push_str(&format!(
+        "Defaults for all of these can be set in {}, next to the TAS tool or in %APPDATA%\\SoulsTAS.\n",
+        CONFIG_FILE_NAME
+    ));
+    text.push_str(&format!(
+        "Running `{} (game) (script)` without a command works the same as `run`.\n",
+        executable
+    ));
+
+    return text;
+}
+
+pub fn actions_help_text() -> String {
+    let mut text = String::from("Actions, used as `(frame) (action)` in TAS scripts:\n");
+
+    for action in ACTIONS {
+        text.push_str(&format!(
+            "  {}\n      {}\n",
+            action.syntax, action.description
+        ));
+    }
+
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, SoulsTasError> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        return parse_args(&args);
+    }
+
+    fn usage_error(args: &str) -> String {
+        return match parse(args) {
+            Err(SoulsTasError::Usage(message)) => message,
+            x => panic!("`{}` should be a usage error, got {:?}", args, x),
+        };
+    }
+
+    #[test]
+    fn run_with_and_without_game() {
+        match parse("run er any%.txt --start-frame 20 --log-level info").unwrap() {
+            Command::Run {
+                game,
+                script,
+                options,
+                start_frame,
+            } => {
+                assert_eq!(game.as_deref(), Some("er"));
+                assert_eq!(script, PathBuf::from("any%.txt"));
+                assert_eq!(start_frame, 20);
+                assert!(matches!(options.log_level, Some(LogLevel::Info)));
+            }
+            x => panic!("{:?}", x),
+        }
+
+        match parse("run any%.txt").unwrap() {
+            Command::Run {
+                game, start_frame, ..
+            } => {
+                assert_eq!(game, None);
+                assert_eq!(start_frame, 0);
+            }
+            x => panic!("{:?}", x),
+        }
+    }
+
+    #[test]
+    fn run_without_subcommand() {
+        match parse("DS3 any%.txt").unwrap() {
+            Command::Run { game, script, .. } => {
+                assert_eq!(game.as_deref(), Some("ds3"));
+                assert_eq!(script, PathBuf::from("any%.txt"));
+            }
+            x => panic!("{:?}", x),
+        }
+
+        assert!(matches!(parse("sim test.txt"), Ok(Command::Run { .. })));
+    }
+
+    #[test]
+    fn launch_options() {
+        match parse(
+            "run sekiro any%.txt --launch 814380 --save-slot 2 --save-dir saves -- -windowed",
+        )
+        .unwrap()
+        {
+            Command::Run { options, .. } => {
+                assert_eq!(options.launch.as_deref(), Some("814380"));
+                assert_eq!(options.launch_args, vec![String::from("-windowed")]);
+                assert_eq!(options.save_slot.as_deref(), Some("2"));
+                assert_eq!(options.save_dir, Some(PathBuf::from("saves")));
+            }
+            x => panic!("{:?}", x),
+        }
+    }
+
+    #[test]
+    fn check_lint_and_fmt() {
+        match parse("check ds1 test.txt").unwrap() {
+            Command::Check { game, script } => {
+                assert_eq!(game.as_deref(), Some("ds1"));
+                assert_eq!(script, PathBuf::from("test.txt"));
+            }
+            x => panic!("{:?}", x),
+        }
+
+        assert!(matches!(parse("lint test.txt"), Ok(Command::Lint { .. })));
+        assert!(matches!(
+            parse("fmt test.txt"),
+            Ok(Command::Fmt { write: false, .. })
+        ));
+        assert!(matches!(
+            parse("fmt test.txt --write"),
+            Ok(Command::Fmt { write: true, .. })
+        ));
+    }
+
+    #[test]
+    fn record_monitor_info_and_help() {
+        match parse("record test.txt out.txt --save run.sl2").unwrap() {
+            Command::Record {
+                game,
+                script,
+                output,
+                options,
+            } => {
+                assert_eq!(game, None);
+                assert_eq!(script, PathBuf::from("test.txt"));
+                assert_eq!(output, PathBuf::from("out.txt"));
+                assert_eq!(options.save, Some(PathBuf::from("run.sl2")));
+            }
+            x => panic!("{:?}", x),
+        }
+
+        match parse("monitor nr --pid 1234").unwrap() {
+            Command::Monitor { game, options } => {
+                assert_eq!(game.as_deref(), Some("nr"));
+                assert_eq!(options.pid, Some(1234));
+            }
+            x => panic!("{:?}", x),
+        }
+        match parse("info").unwrap() {
+            Command::Info { game } => assert_eq!(game, None),
+            x => panic!("{:?}", x),
+        }
+        match parse("help Actions").unwrap() {
+            Command::Help { topic } => assert_eq!(topic.as_deref(), Some("actions")),
+            x => panic!("{:?}", x),
+        }
+
+        assert!(matches!(parse(""), Ok(Command::Help { topic: None })));
+        assert!(matches!(
+            parse("run test.txt --help"),
+            Ok(Command::Help { topic: None })
+        ));
+    }
+
+    #[test]
+    fn bad_arguments() {
+        assert_eq!(usage_error("run test.txt --fast"), "Unknown option --fast.");
+        assert_eq!(usage_error("jump test.txt"), "Unknown command jump.");
+        assert_eq!(
+            usage_error("run test.txt --start-frame"),
+            "Invalid value for --start-frame."
+        );
+        assert_eq!(
+            usage_error("run test.txt --start-frame -5"),
+            "Invalid value for --start-frame."
+        );
+        assert_eq!(
+            usage_error("run test.txt --log-level loud"),
+            "Invalid log level loud, use error, info or frame."
+        );
+        assert_eq!(
+            usage_error("run test.txt -- -windowed"),
+            "Game arguments after -- need --launch."
+        );
+        assert_eq!(
+            usage_error("run test.txt --pid er"),
+            "Invalid value for --pid."
+        );
+        assert_eq!(
+            usage_error("run er test.txt --pid 1234 --launch 1245620"),
+            "--launch and --pid can't be used together."
+        );
+        assert_eq!(usage_error("run"), "Invalid argument count.");
+        assert_eq!(usage_error("record test.txt"), "Invalid argument count.");
+        assert_eq!(usage_error("lint a.txt b.txt"), "Invalid argument count.");
+        assert_eq!(usage_error("info er ds3"), "Invalid argument count.");
+    }
+
+    #[test]
+    fn config_game_is_only_a_fallback() {
+        let config = Config {
+            game: Some(String::from("ds3")),
+            ..Config::default()
+        };
+
+        let mut command = parse("run test.txt").unwrap();
+        apply_config(&mut command, &config);
+        assert!(matches!(command, Command::Run { game: Some(x), .. } if x == "ds3"));
+
+        let mut command = parse("run er test.txt").unwrap();
+        apply_config(&mut command, &config);
+        assert!(matches!(command, Command::Run { game: Some(x), .. } if x == "er"));
+    }
+
+    #[test]
+    fn pid_skips_launching_from_the_config() {
+        let game_config = GameConfig {
+            game_type: GameType::EldenRing,
+            launch: Some(String::from("1245620")),
+            launch_args: vec![String::from("-windowed")],
+            save_dir: None,
+            save_slot: Some(String::from("1")),
+        };
+
+        let options = AttachOptions::default().with_game_config(Some(&game_config));
+        assert_eq!(options.launch.as_deref(), Some("1245620"));
+        assert_eq!(options.launch_args, vec![String::from("-windowed")]);
+
+        let options = AttachOptions {
+            pid: Some(1234),
+            ..AttachOptions::default()
+        }
+        .with_game_config(Some(&game_config));
+        assert_eq!(options.launch, None);
+        assert!(options.launch_args.is_empty());
+        assert_eq!(options.save_slot.as_deref(), Some("1"));
+    }
+}
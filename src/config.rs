@@ -0,0 +1,218 @@
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::*;
+use crate::games::registry::*;
+
+// Settings from soulstas.toml, command line options override them.
+// The one next to the executable wins over the one in the user config folder.
+
+pub const CONFIG_FILE_NAME: &str = "soulstas.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error, // Only errors and warnings
+    Info,  // Also traces and other messages
+    Frame, // Also every frame number
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub game: Option<String>,     // Used when no game is given
+    pub dll_dir: Option<PathBuf>, // Where the DLLs to inject are, instead of next to the TAS tool
+    pub log_level: Option<LogLevel>,
+    pub games: Vec<GameConfig>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub game_type: GameType,
+    pub launch: Option<String>,
+    pub launch_args: Vec<String>,
+    pub save_dir: Option<PathBuf>, // Folder with the account folders in it
+    pub save_slot: Option<String>,
+}
+
+// soulstas.toml as it's written, unknown settings are errors so typos don't get ignored silently
+#[derive(Deserialize)]
+struct ConfigFile {
+    game: Option<String>,
+    dll_dir: Option<String>,
+    log_level: Option<String>,
+    #[serde(flatten)]
+    games: toml::Table,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameConfigFile {
+    launch: Option<String>,
+    #[serde(default)]
+    launch_args: Vec<String>,
+    save_dir: Option<String>,
+    save_slot: Option<String>,
+}
+
+impl Config {
+    // Loads all config files that exist, missing ones are fine
+    pub fn load() -> Result<Config, SoulsTasError> {
+        let mut config = Config::default();
+
+        for path in config_paths() {
+            if path.is_file() {
+                config.merge(Config::load_file(&path)?);
+            }
+        }
+
+        return Ok(config);
+    }
+
+    pub fn load_file(path: &Path) -> Result<Config, SoulsTasError> {
+        let error = |err: String| SoulsTasError::Usage(format!("{}: {}", path.display(), err));
+
+        let text = read_to_string(path).map_err(|err| error(err.to_string()))?;
+        let file: ConfigFile = toml::from_str(&text).map_err(|err| error(err.to_string()))?;
+
+        let mut config = Config {
+            game: file.game,
+            dll_dir: file.dll_dir.map(|x| relative_to(path, &x)),
+            log_level: match file.log_level {
+                Some(x) => Some(string_to_log_level(&x).ok_or(error(format!(
+                    "Invalid log_level {}, use error, info or frame",
+                    x
+                )))?),
+                None => None,
+            },
+            games: Vec::new(),
+        };
+
+        // Every other table is a game, by any of its names
+        for (name, value) in file.games {
+            let info =
+                string_to_game(&name).ok_or(error(format!("Unknown setting or game {}", name)))?;
+            let game_file: GameConfigFile = value
+                .try_into()
+                .map_err(|err: toml::de::Error| error(format!("[{}] {}", name, err)))?;
+
+            config.merge_game(GameConfig {
+                game_type: info.game_type,
+                launch: game_file.launch,
+                launch_args: game_file.launch_args,
+                save_dir: game_file.save_dir.map(|x| relative_to(path, &x)),
+                save_slot: game_file.save_slot,
+            });
+        }
+
+        return Ok(config);
+    }
+
+    // Values from the other config replace the ones in this one, setting by setting
+    fn merge(&mut self, other: Config) {
+        self.game = other.game.or(self.game.take());
+        self.dll_dir = other.dll_dir.or(self.dll_dir.take());
+        self.log_level = other.log_level.or(self.log_level);
+
+        for game_config in other.games {
+            self.merge_game(game_config);
+        }
+    }
+
+    fn merge_game(&mut self, other: GameConfig) {
+        let game_config = match self
+            .games
+            .iter_mut()
+            .find(|x| x.game_type == other.game_type)
+        {
+            Some(x) => x,
+            None => {
+                self.games.push(other);
+                return;
+            }
+        };
+
+        game_config.launch = other.launch.or(game_config.launch.take());
+        if !other.launch_args.is_empty() {
+            game_config.launch_args = other.launch_args;
+        }
+        game_config.save_dir = other.save_dir.or(game_config.save_dir.take());
+        game_config.save_slot = other.save_slot.or(game_config.save_slot.take());
+    }
+
+    pub fn game(&self, game_type: GameType) -> Option<&GameConfig> {
+        return self.games.iter().find(|x| x.game_type == game_type);
+    }
+}
+
+// Lowest priority first
+pub fn config_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+
+    if let Ok(app_data) = env::var("APPDATA") {
+        paths.push(
+            PathBuf::from(app_data)
+                .join("SoulsTAS")
+                .join(CONFIG_FILE_NAME),
+        );
+    }
+    if let Some(exe_dir) = env::current_exe()
+        .ok()
+        .and_then(|x| x.parent().map(|x| x.to_path_buf()))
+    {
+        paths.push(exe_dir.join(CONFIG_FILE_NAME));
+    }
+
+    return paths;
+}
+
+pub fn string_to_log_level(name: &str) -> Option<LogLevel> {
+    return match name.to_lowercase().as_str() {
+        "error" => Some(LogLevel::Error),
+        "info" => Some(LogLevel::Info),
+        "frame" => Some(LogLevel::Frame),
+        _ => None,
+    };
+}
+
+// Relative paths are from the folder the config file is in
+fn relative_to(config_path: &Path, path: &str) -> PathBuf {
+    return config_path.parent().unwrap_or(Path::new("")).join(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_config(launch: Option<&str>, save_slot: Option<&str>) -> GameConfig {
+        return GameConfig {
+            game_type: GameType::EldenRing,
+            launch: launch.map(String::from),
+            launch_args: Vec::new(),
+            save_dir: None,
+            save_slot: save_slot.map(String::from),
+        };
+    }
+
+    #[test]
+    fn merge_keeps_settings_the_other_config_doesnt_have() {
+        let mut config = Config {
+            log_level: Some(LogLevel::Frame),
+            games: vec![game_config(Some("1245620"), Some("slot_a"))],
+            ..Config::default()
+        };
+        config.merge(Config {
+            game: Some(String::from("er")),
+            games: vec![game_config(None, Some("slot_b"))],
+            ..Config::default()
+        });
+
+        assert_eq!(config.game.as_deref(), Some("er"));
+        assert_eq!(config.log_level, Some(LogLevel::Frame));
+        assert_eq!(config.games.len(), 1);
+        let game = config.game(GameType::EldenRing).unwrap();
+        assert_eq!(game.launch.as_deref(), Some("1245620"));
+        assert_eq!(game.save_slot.as_deref(), Some("slot_b"));
+    }
+}
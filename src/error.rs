@@ -0,0 +1,66 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::games::shared::GameError;
+
+// Everything that can make the TAS tool fail, each kind with its own exit code
+
+#[derive(Debug, Clone)]
+pub enum SoulsTasError {
+    Usage(String),       // Wrong command line arguments
+    Script(String),      // TAS script missing or invalid
+    Unsupported(String), // TAS script uses something the game doesn't support
+    Attach(String),      // Game process not found or not accessible
+    Injection(String),   // DLL couldn't be injected or is missing an export
+    // AoB scan didn't find anything
+    Signature {
+        name: String,    // Name of the pointer
+        module: String,  // Executable it was searched in
+        version: String, // Version of the executable
+    },
+    Runtime(GameError), // Something went wrong while the TAS was running
+    Save(String),       // Save file couldn't be staged or restored
+    Io(String),         // Output file couldn't be written
+}
+
+impl SoulsTasError {
+    pub fn exit_code(&self) -> i32 {
+        return match self {
+            SoulsTasError::Usage(_) => 2,
+            SoulsTasError::Script(_) => 3,
+            SoulsTasError::Unsupported(_) => 4,
+            SoulsTasError::Attach(_) => 5,
+            SoulsTasError::Injection(_) => 6,
+            SoulsTasError::Signature { .. } => 7,
+            SoulsTasError::Runtime(GameError::Aborted) => 130, // Same as a shell would use for Ctrl+C
+            SoulsTasError::Runtime(_) => 8,
+            SoulsTasError::Save(_) => 9,
+            SoulsTasError::Io(_) => 10,
+        };
+    }
+}
+
+impl Display for SoulsTasError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SoulsTasError::Usage(message) => write!(f, "{}", message),
+            SoulsTasError::Script(message) => write!(f, "{}", message),
+            SoulsTasError::Unsupported(message) => write!(f, "{}", message),
+            SoulsTasError::Attach(message) => write!(f, "{}", message),
+            SoulsTasError::Injection(message) => write!(f, "{}", message),
+            SoulsTasError::Signature {
+                name,
+                module,
+                version,
+            } => write!(f, "AoB `{}` not found in {} {}", name, module, version),
+            SoulsTasError::Runtime(err) => write!(f, "{}", err),
+            SoulsTasError::Save(message) => write!(f, "{}", message),
+            SoulsTasError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<GameError> for SoulsTasError {
+    fn from(err: GameError) -> Self {
+        return SoulsTasError::Runtime(err);
+    }
+}
@@ -1,8 +1,13 @@
 use mem_rs::prelude::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -13,109 +18,164 @@ struct GamePointers {
     save_active: Pointer,
     cutscene_3d: Pointer,
     cutscene_briefing: Pointer,
+    screen_state: Option<Pointer>,
+    position: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
 
-pub unsafe fn armoredcore6_init(process: &mut Process) -> GameFuncs {
+pub struct ArmoredCore6 {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl ArmoredCore6 {
+    pub unsafe fn new(mut process: Process) -> Result<ArmoredCore6, SoulsTasError> {
+        let capabilities = armoredcore6_init(&mut process)?;
+        return Ok(ArmoredCore6 {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for ArmoredCore6 {
+    fn name(&self) -> &'static str {
+        return GameType::ArmoredCore6.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        // No gamepad support, keyboard and mouse only
+        return Box::new(SendInputSink);
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { armoredcore6_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { armoredcore6_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { armoredcore6_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { armoredcore6_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { armoredcore6_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { armoredcore6_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(armoredcore6_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(armoredcore6_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(armoredcore6_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { armoredcore6_flag_loading(&mut self.process) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { armoredcore6_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { armoredcore6_read_position(&mut self.process) };
+    }
+}
+
+pub unsafe fn armoredcore6_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "AC6_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::ArmoredCore6);
     POINTERS = Some(GamePointers {
         fps_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "AC6_FPS_PATCH_ENABLED")
-                .expect("Couldn't find AC6_FPS_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "AC6_FPS_PATCH_ENABLED")?,
             vec![0],
         ),
         fps_limit: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "AC6_FPS_CUSTOM_LIMIT")
-                .expect("Couldn't find AC6_FPS_CUSTOM_LIMIT")
-                .addr,
+            find_export(&soulstas_patches_exports, "AC6_FPS_CUSTOM_LIMIT")?,
             vec![0],
         ),
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "AC6_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find AC6_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "AC6_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "AC6_FRAME_RUNNING")
-                .expect("Couldn't find AC6_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "AC6_FRAME_RUNNING")?,
             vec![0],
         ),
-        input_state: process
-            .scan_rel(
-                "input_state",
-                "48 8b 1d ? ? ? ? 0f 28 00 66 0f 7f 45 f7 48 85 db",
-                3,
-                7,
-                vec![0, 0xA5A0, 0x80, 0x118],
-            )
-            .expect("Couldn't find input_state pointer"),
-        save_active: process
-            .scan_rel(
-                "save_active",
-                "48 8b 05 ? ? ? ? 48 8b 10 48 83 c2 19 41 b8 10 00 00 00 48 8d 4d 97",
-                3,
-                7,
-                vec![0, 0x8, 0x8],
-            )
-            .expect("Couldn't find save_active pointer"),
-        cutscene_3d: process
-            .scan_rel(
-                "cutscene_3d",
-                "48 39 1d ? ? ? ? 48 8b 4b 18 75 11 45 33 c0",
-                3,
-                7,
-                vec![0, 0x114],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
-        cutscene_briefing: process
-            .scan_rel(
-                "cutscene_briefing",
-                "48 8b 15 ? ? ? ? 44 8d 4e 03 48 8b 82 90 06 00 00",
-                3,
-                7,
-                vec![0, 0x140, 0x78, 0x98, 0xa8],
-            )
-            .expect("Couldn't find cutscene_briefing pointer"),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_briefing: scan_signature(process, &signatures, "cutscene_briefing")?,
+        screen_state: scan_optional_signature(process, &signatures, "screen_state"),
+        position: scan_optional_signature(process, &signatures, "position"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: armoredcore6_script_start,
-        script_end: armoredcore6_script_end,
-        frame_next: armoredcore6_frame_next,
-        frame_start: armoredcore6_frame_start,
-        frame_end: armoredcore6_frame_end,
-        action_fps: armoredcore6_action_fps,
-        flag_frame: armoredcore6_flag_frame,
-        flag_ingame: armoredcore6_flag_ingame,
-        flag_cutscene: armoredcore6_flag_cutscene,
-        flag_mainmenu: armoredcore6_flag_mainmenu,
-        flag_position: armoredcore6_flag_position,
-        flag_position_alternative: armoredcore6_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    let mut capabilities = found_capabilities(
+        GameType::ArmoredCore6,
+        &[
+            (Capability::Loading, &pointers.screen_state),
+            (Capability::Position, &pointers.position),
+        ],
+    );
+
+    // Same for the FPS patch, soulstas-patches skips it if its scan failed
+    for export_name in [
+        "AC6_FPS_HOOKED",
+        "AC6_FPS_HISTORY_HOOKED",
+        "AC6_FPS_CUSTOM_LIMIT_HOOKED",
+    ] {
+        if !patch_hooked(process, &soulstas_patches_exports, export_name)? {
+            capabilities.retain(|x| *x != Capability::Fps);
+        }
+    }
+
+    return Ok(capabilities);
 }
 
 pub unsafe fn armoredcore6_script_start(process: &mut Process) {
@@ -192,24 +252,24 @@ pub unsafe fn armoredcore6_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn armoredcore6_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn armoredcore6_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let screen_state_pointer = optional_pointer(&pointers.screen_state, Capability::Loading)?;
+    if screen_state_pointer.read_i32_rel(None) == 0 {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
 }
 
-pub unsafe fn armoredcore6_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn armoredcore6_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
 }
@@ -1,10 +1,13 @@
 use mem_rs::prelude::*;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     frame_advance: Pointer,
@@ -17,6 +20,8 @@ struct GamePointers {
     cutscene_movie: Pointer,
     gamepad_index: Pointer,
     gamepad_flags: Pointer,
+    loading: Option<Pointer>,
+    position: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
@@ -25,117 +30,141 @@ static mut POINTERS: Option<GamePointers> = None;
 static mut GAMEPAD_INDEX_ORIG: i32 = 0;
 static mut GAMEPAD_FLAGS_ORIG: u32 = 0;
 
-pub unsafe fn ds1_init(process: &mut Process) -> GameFuncs {
+pub struct DarkSouls1 {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl DarkSouls1 {
+    pub unsafe fn new(mut process: Process) -> Result<DarkSouls1, SoulsTasError> {
+        let capabilities = ds1_init(&mut process)?;
+        return Ok(DarkSouls1 {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for DarkSouls1 {
+    fn name(&self) -> &'static str {
+        return GameType::DarkSouls1.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { ds1_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { ds1_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { ds1_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { ds1_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { ds1_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { ds1_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { ds1_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { ds1_flag_loading(&mut self.process) };
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { ds1_read_position(&mut self.process) };
+    }
+}
+
+pub unsafe fn ds1_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "DS1_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::DarkSouls1);
     POINTERS = Some(GamePointers {
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find DS1_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1_FRAME_RUNNING")
-                .expect("Couldn't find DS1_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1_FRAME_RUNNING")?,
             vec![0],
         ),
         xinput_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1_XINPUT_PATCH_ENABLED")
-                .expect("Couldn't find DS1_XINPUT_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1_XINPUT_PATCH_ENABLED")?,
             vec![0],
         ),
         xinput_state: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1_XINPUT_STATE")
-                .expect("Couldn't find DS1_XINPUT_STATE")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1_XINPUT_STATE")?,
             vec![0],
         ),
-        input_state: process
-            .scan_abs(
-                "input_state",
-                "a1 ? ? ? ? 83 ec 28 53 c7 47 08 00 00 00 00 8b 58 3c",
-                1,
-                vec![0, 0, 0x3c, 0x28, 0xc0],
-            )
-            .expect("Couldn't find input_state pointer"),
-        save_active: process
-            .scan_abs(
-                "save_active",
-                "8b 15 ? ? ? ? 8a 4a 04 80 f9 ff 74 0f 80 f9 01 75 04 8a c1 59 c3",
-                2,
-                vec![0, 0, 0x928],
-            )
-            .expect("Couldn't find save_active pointer"),
-        cutscene_3d: process
-            .scan_abs(
-                "cutscene_3d",
-                "8b 0d ? ? ? ? 0f 57 c0 0f 2f 41 30 72 12 8b 15 ? ? ? ? 89 9a dc 02 00 00",
-                2,
-                vec![0, 0, 0x154],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
-        cutscene_movie: process
-            .scan_abs(
-                "cutscene_movie",
-                "a3 ? ? ? ? e8 ? ? ? ? 5f 89 86 f4 00 00 00 5e c3 cc 6a",
-                1,
-                vec![0, 0, 0xf4, 0x93d],
-            )
-            .expect("Couldn't find cutscene_movie pointer"),
-        gamepad_index: process
-            .scan_abs(
-                "gamepad_index",
-                "8b 15 ? ? ? ? f2 0f 5e c8 f2 0f 5a c9 f3 0f 11 4a 34",
-                2,
-                vec![0, 0, 0x8, 0x8, 0x164],
-            )
-            .expect("Couldn't find gamepad_index pointer"),
-        gamepad_flags: process
-            .scan_abs(
-                "gamepad_flags",
-                "8b 15 ? ? ? ? f2 0f 5e c8 f2 0f 5a c9 f3 0f 11 4a 34",
-                2,
-                vec![0, 0, 0x8, 0x8, 0x194],
-            )
-            .expect("Couldn't find gamepad_flags pointer"),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_movie: scan_signature(process, &signatures, "cutscene_movie")?,
+        gamepad_index: scan_signature(process, &signatures, "gamepad_index")?,
+        gamepad_flags: scan_signature(process, &signatures, "gamepad_flags")?,
+        loading: scan_optional_signature(process, &signatures, "loading"),
+        position: scan_optional_signature(process, &signatures, "position"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: ds1_script_start,
-        script_end: ds1_script_end,
-        frame_next: ds1_frame_next,
-        frame_start: ds1_frame_start,
-        frame_end: ds1_frame_end,
-        action_fps: ds1_action_fps,
-        flag_frame: ds1_flag_frame,
-        flag_ingame: ds1_flag_ingame,
-        flag_cutscene: ds1_flag_cutscene,
-        flag_mainmenu: ds1_flag_mainmenu,
-        flag_position: ds1_flag_position,
-        flag_position_alternative: ds1_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    return Ok(found_capabilities(
+        GameType::DarkSouls1,
+        &[
+            (Capability::Loading, &pointers.loading),
+            (Capability::Position, &pointers.position),
+        ],
+    ));
 }
 
 // TODO: Disable FPS check/kick
@@ -178,16 +207,12 @@ pub unsafe fn ds1_frame_end(process: &mut Process) {
     // Set correct gamepad flags
     pointers.gamepad_index.write_i32_rel(None, 999);
     pointers.gamepad_flags.write_u32_rel(None, 795);
-
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
 }
 
-pub unsafe fn ds1_action_fps(process: &mut Process, fps: f32) {}
+pub unsafe fn ds1_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
+}
 
 pub unsafe fn ds1_flag_frame(process: &mut Process) -> bool {
     let pointers = POINTERS.as_ref().unwrap();
@@ -224,18 +249,24 @@ pub unsafe fn ds1_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn ds1_flag_position(process: &mut Process, x: f32, y: f32, z: f32, range: f32) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds1_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let loading_pointer = optional_pointer(&pointers.loading, Capability::Loading)?;
+    if loading_pointer.read_bool_rel(None) {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
 }
 
-pub unsafe fn ds1_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds1_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
 }
@@ -1,12 +1,14 @@
 use mem_rs::prelude::*;
-use std::path::PathBuf;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::geometry::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
-use crate::utils::version::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     frame_advance: Pointer,
@@ -19,6 +21,13 @@ struct GamePointers {
     cutscene_movie: Pointer,
     gamepad_index: Pointer,
     gamepad_flags: Pointer,
+    event_flags: Option<Pointer>,
+    animation: Option<Pointer>,
+    area: Option<Pointer>,
+    hp: Option<Pointer>,
+    loading: Option<Pointer>,
+    position: Option<Pointer>,
+    heading: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
@@ -27,138 +36,171 @@ static mut POINTERS: Option<GamePointers> = None;
 static mut GAMEPAD_INDEX_ORIG: i32 = 0;
 static mut GAMEPAD_FLAGS_ORIG: u32 = 0;
 
-pub unsafe fn ds1r_init(process: &mut Process) -> GameFuncs {
+pub struct DarkSouls1Remastered {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl DarkSouls1Remastered {
+    pub unsafe fn new(mut process: Process) -> Result<DarkSouls1Remastered, SoulsTasError> {
+        let capabilities = ds1r_init(&mut process)?;
+        return Ok(DarkSouls1Remastered {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for DarkSouls1Remastered {
+    fn name(&self) -> &'static str {
+        return GameType::DarkSouls1Remastered.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { ds1r_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { ds1r_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { ds1r_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { ds1r_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { ds1r_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { ds1r_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { ds1r_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1r_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1r_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds1r_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { ds1r_flag_loading(&mut self.process) };
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { ds1r_read_position(&mut self.process) };
+    }
+
+    fn read_heading(&mut self) -> Result<f32, GameError> {
+        return unsafe { ds1r_read_heading(&mut self.process) };
+    }
+
+    fn flag_event(&mut self, id: u32) -> Result<bool, GameError> {
+        return unsafe { ds1r_flag_event(&mut self.process, id) };
+    }
+
+    fn read_animation(&mut self) -> Result<i32, GameError> {
+        return unsafe { ds1r_read_animation(&mut self.process) };
+    }
+
+    fn read_area(&mut self) -> Result<[u8; 4], GameError> {
+        return unsafe { ds1r_read_area(&mut self.process) };
+    }
+
+    fn flag_zero_hp(&mut self) -> Result<bool, GameError> {
+        return unsafe { ds1r_flag_zero_hp(&mut self.process) };
+    }
+}
+
+pub unsafe fn ds1r_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
-
-    // Determine playerctrl offset based depending on version
-    let process_version = Version::from_file_version_info(PathBuf::from(process.get_path()));
-    let playerctrl_offset: usize = if process_version
-        <= (Version {
-            major: 1,
-            minor: 3,
-            build: 0,
-            revision: 0,
-        }) {
-        // Pre-1.03.0
-        0x48
-    } else {
-        0x68
-    };
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "DS1R_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::DarkSouls1Remastered);
     POINTERS = Some(GamePointers {
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1R_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find DS1R_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1R_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1R_FRAME_RUNNING")
-                .expect("Couldn't find DS1R_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1R_FRAME_RUNNING")?,
             vec![0],
         ),
         xinput_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1R_XINPUT_PATCH_ENABLED")
-                .expect("Couldn't find DS1R_XINPUT_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1R_XINPUT_PATCH_ENABLED")?,
             vec![0],
         ),
         xinput_state: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS1R_XINPUT_STATE")
-                .expect("Couldn't find DS1R_XINPUT_STATE")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS1R_XINPUT_STATE")?,
             vec![0],
         ),
-        input_state: process
-            .scan_rel(
-                "input_state",
-                "48 8b 05 ? ? ? ? 33 ff 83 cd ff 45 0f b6 f0 44 8b fa",
-                3,
-                7,
-                vec![0, 0x68, playerctrl_offset, 0x100],
-            )
-            .expect("Couldn't find input_state pointer"),
-        save_active: process
-            .scan_rel(
-                "save_active",
-                "48 8b 05 ? ? ? ? 48 8b 58 10 48 8b 05 ? ? ? ? 48 8b 78 68",
-                3,
-                7,
-                vec![0, 0xd20],
-            )
-            .expect("Couldn't find save_active pointer"),
-        cutscene_3d: process
-            .scan_rel(
-                "cutscene_3d",
-                "48 8b 05 ? ? ? ? 0f 28 80 60 01 00 00 48 8b c1 66 0f 7f 01",
-                3,
-                7,
-                vec![0, 0x154],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
-        cutscene_movie: process
-            .scan_rel(
-                "cutscene_movie",
-                "48 89 05 ? ? ? ? 48 8b cf e8 ? ? ? ? 48 89 87 08 02 00 00",
-                3,
-                7,
-                vec![0, 0x60, 0x350],
-            )
-            .expect("Couldn't find cutscene_movie pointer"),
-        gamepad_index: process
-            .scan_rel(
-                "gamepad_index",
-                "48 8b 05 ? ? ? ? 48 8b 48 10 80 79 28 00 75 0e 0f b6 59 28",
-                3,
-                7,
-                vec![0, 0x10, 0x10, 0x264],
-            )
-            .expect("Couldn't find gamepad_index pointer"),
-        gamepad_flags: process
-            .scan_rel(
-                "gamepad_flags",
-                "48 8b 05 ? ? ? ? 48 8b 48 10 80 79 28 00 75 0e 0f b6 59 28",
-                3,
-                7,
-                vec![0, 0x10, 0x10, 0x2dc],
-            )
-            .expect("Couldn't find gamepad_flags pointer"),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_movie: scan_signature(process, &signatures, "cutscene_movie")?,
+        gamepad_index: scan_signature(process, &signatures, "gamepad_index")?,
+        gamepad_flags: scan_signature(process, &signatures, "gamepad_flags")?,
+        event_flags: scan_optional_signature(process, &signatures, "event_flags"),
+        animation: scan_optional_signature(process, &signatures, "animation"),
+        area: scan_optional_signature(process, &signatures, "area"),
+        hp: scan_optional_signature(process, &signatures, "hp"),
+        loading: scan_optional_signature(process, &signatures, "loading"),
+        position: scan_optional_signature(process, &signatures, "position"),
+        heading: scan_optional_signature(process, &signatures, "heading"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: ds1r_script_start,
-        script_end: ds1r_script_end,
-        frame_next: ds1r_frame_next,
-        frame_start: ds1r_frame_start,
-        frame_end: ds1r_frame_end,
-        action_fps: ds1r_action_fps,
-        flag_frame: ds1r_flag_frame,
-        flag_ingame: ds1r_flag_ingame,
-        flag_cutscene: ds1r_flag_cutscene,
-        flag_mainmenu: ds1r_flag_mainmenu,
-        flag_position: ds1r_flag_position,
-        flag_position_alternative: ds1r_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    return Ok(found_capabilities(
+        GameType::DarkSouls1Remastered,
+        &[
+            (Capability::Loading, &pointers.loading),
+            (Capability::EventFlags, &pointers.event_flags),
+            (Capability::Animation, &pointers.animation),
+            (Capability::Area, &pointers.area),
+            (Capability::Hp, &pointers.hp),
+            (Capability::Position, &pointers.position),
+            (Capability::Heading, &pointers.heading),
+        ],
+    ));
 }
 
 pub unsafe fn ds1r_script_start(process: &mut Process) {
@@ -200,16 +242,12 @@ pub unsafe fn ds1r_frame_end(process: &mut Process) {
     // Set correct gamepad flags
     pointers.gamepad_index.write_i32_rel(None, 999);
     pointers.gamepad_flags.write_u32_rel(None, 795);
-
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
 }
 
-pub unsafe fn ds1r_action_fps(process: &mut Process, fps: f32) {}
+pub unsafe fn ds1r_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
+}
 
 pub unsafe fn ds1r_flag_frame(process: &mut Process) -> bool {
     let pointers = POINTERS.as_ref().unwrap();
@@ -246,24 +284,131 @@ pub unsafe fn ds1r_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn ds1r_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds1r_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let loading_pointer = optional_pointer(&pointers.loading, Capability::Loading)?;
+    if loading_pointer.read_bool_rel(None) {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
+}
+
+pub unsafe fn ds1r_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
+}
+
+pub unsafe fn ds1r_read_heading(process: &mut Process) -> Result<f32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let heading_pointer = optional_pointer(&pointers.heading, Capability::Heading)?;
+
+    let mut angle_buffer: [u8; 4] = [0; 4];
+    if !heading_pointer.read_memory_rel(None, &mut angle_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(radians_to_heading(f32::from_ne_bytes(angle_buffer)));
 }
 
-pub unsafe fn ds1r_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+// Based on SoulSplitter's event flag reading
+pub unsafe fn ds1r_flag_event(process: &mut Process, id: u32) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let event_flags_pointer = optional_pointer(&pointers.event_flags, Capability::EventFlags)?;
+
+    // Flag IDs are laid out as GAAASNNN (group, area, section, number)
+    let group_offset: usize = match id / 10000000 {
+        0 => 0x00000,
+        1 => 0x00500,
+        5 => 0x05F00,
+        6 => 0x0B900,
+        7 => 0x11300,
+        _ => return Ok(false),
+    };
+
+    let area_index: usize = match (id / 10000) % 1000 {
+        0 => 0,
+        100 => 1,
+        101 => 2,
+        102 => 3,
+        110 => 4,
+        120 => 5,
+        121 => 6,
+        130 => 7,
+        131 => 8,
+        132 => 9,
+        140 => 10,
+        141 => 11,
+        150 => 12,
+        151 => 13,
+        160 => 14,
+        170 => 15,
+        180 => 16,
+        181 => 17,
+        _ => return Ok(false),
+    };
+
+    let section = ((id / 1000) % 10) as usize;
+    let number = id % 1000;
+
+    let offset = group_offset + area_index * 0x500 + section * 128 + (number as usize / 32) * 4;
+    let flags = event_flags_pointer.read_u32_rel(Some(offset));
+    let flag = flags >> (31 - (number % 32)) & 1 == 1;
+
+    return Ok(flag);
+}
+
+pub unsafe fn ds1r_read_animation(process: &mut Process) -> Result<i32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let animation_pointer = optional_pointer(&pointers.animation, Capability::Animation)?;
+
+    let mut animation_buffer: [u8; 4] = [0; 4];
+    if !animation_pointer.read_memory_rel(None, &mut animation_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(i32::from_ne_bytes(animation_buffer));
+}
+
+pub unsafe fn ds1r_flag_zero_hp(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let hp_pointer = optional_pointer(&pointers.hp, Capability::Hp)?;
+
+    // No character loaded, so no HP either
+    if pointers.save_active.read_i32_rel(None) == -1 {
+        return Ok(false);
+    }
+
+    let mut hp_buffer: [u8; 4] = [0; 4];
+    if !hp_pointer.read_memory_rel(None, &mut hp_buffer) {
+        return Ok(false);
+    }
+
+    let hp = i32::from_ne_bytes(hp_buffer);
+    return Ok(hp <= 0);
+}
+
+pub unsafe fn ds1r_read_area(process: &mut Process) -> Result<[u8; 4], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let area_pointer = optional_pointer(&pointers.area, Capability::Area)?;
+
+    let mut area_buffer: [u8; 4] = [0; 4];
+    if !area_pointer.read_memory_rel(None, &mut area_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    // Stored as 0xAABBCCDD, -1 while not in any map
+    let area = u32::from_ne_bytes(area_buffer);
+    if area == u32::MAX {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(area.to_be_bytes());
 }
@@ -1,12 +1,13 @@
 use mem_rs::prelude::*;
-use std::path::PathBuf;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
-use crate::utils::version::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -18,127 +19,159 @@ struct GamePointers {
     game_state: Pointer,
     cutscene_3d: Pointer,
     cutscene_movie: Pointer,
+    position: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
 
-pub unsafe fn ds2_init(process: &mut Process) -> GameFuncs {
+pub struct DarkSouls2 {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl DarkSouls2 {
+    pub unsafe fn new(mut process: Process) -> Result<DarkSouls2, SoulsTasError> {
+        let capabilities = ds2_init(&mut process)?;
+        return Ok(DarkSouls2 {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for DarkSouls2 {
+    fn name(&self) -> &'static str {
+        return GameType::DarkSouls2.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { ds2_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { ds2_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { ds2_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { ds2_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { ds2_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { ds2_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { ds2_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2_flag_loading(&mut self.process)) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { ds2_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { ds2_read_position(&mut self.process) };
+    }
+}
+
+pub unsafe fn ds2_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
-
-    let process_version = Version::from_file_version_info(PathBuf::from(process.get_path()));
-    let cutscene_movie_offset: usize = if process_version
-        >= (Version {
-            major: 1,
-            minor: 0,
-            build: 4,
-            revision: 0,
-        }) {
-        // 1.04+
-        0xd8
-    } else {
-        0xd4
-    };
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "DS2_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::DarkSouls2);
     POINTERS = Some(GamePointers {
         fps_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_FPS_PATCH_ENABLED")
-                .expect("Couldn't find DS2FPS_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_FPS_PATCH_ENABLED")?,
             vec![0],
         ),
         fps_limit: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_FPS_CUSTOM_LIMIT")
-                .expect("Couldn't find DS2_FPS_CUSTOM_LIMIT")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_FPS_CUSTOM_LIMIT")?,
             vec![0],
         ),
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find DS2_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_FRAME_RUNNING")
-                .expect("Couldn't find DS2_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_FRAME_RUNNING")?,
             vec![0],
         ),
         xinput_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_XINPUT_PATCH_ENABLED")
-                .expect("Couldn't find DS2_XINPUT_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_XINPUT_PATCH_ENABLED")?,
             vec![0],
         ),
         xinput_state: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2_XINPUT_STATE")
-                .expect("Couldn't find DS2_XINPUT_STATE")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2_XINPUT_STATE")?,
             vec![0],
         ),
-        game_state: process
-            .scan_abs(
-                "game_state",
-                "8b 15 ? ? ? ? 51 8b 4a 1c e8 ? ? ? ? 8b 8d fc fe ff ff",
-                2,
-                vec![0, 0, 0xdec],
-            )
-            .expect("Couldn't find game_state pointer"),
-        cutscene_3d: process
-            .scan_abs(
-                "cutscene_3d",
-                "8b 15 ? ? ? ? 51 8b 4a 1c e8 ? ? ? ? 8b 8d fc fe ff ff",
-                2,
-                vec![0, 0, 0x460, 0x14, 0x24],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
-        cutscene_movie: process
-            .scan_abs(
-                "cutscene_movie",
-                "A1 ? ? ? ? 89 4D ? 8B 4B 10 56 57",
-                1,
-                vec![0, 0, 0x4, 0x18, 0x1c, 0x10, cutscene_movie_offset, 0xc],
-            )
-            .expect("Couldn't find cutscene_movie pointer"),
+        game_state: scan_signature(process, &signatures, "game_state")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_movie: scan_signature(process, &signatures, "cutscene_movie")?,
+        position: scan_optional_signature(process, &signatures, "position"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: ds2_script_start,
-        script_end: ds2_script_end,
-        frame_next: ds2_frame_next,
-        frame_start: ds2_frame_start,
-        frame_end: ds2_frame_end,
-        action_fps: ds2_action_fps,
-        flag_frame: ds2_flag_frame,
-        flag_ingame: ds2_flag_ingame,
-        flag_cutscene: ds2_flag_cutscene,
-        flag_mainmenu: ds2_flag_mainmenu,
-        flag_position: ds2_flag_position,
-        flag_position_alternative: ds2_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    let mut capabilities = found_capabilities(
+        GameType::DarkSouls2,
+        &[(Capability::Position, &pointers.position)],
+    );
+
+    // Same for the FPS patch, soulstas-patches skips it if its scan failed
+    if !patch_hooked(process, &soulstas_patches_exports, "DS2_FPS_HOOKED")? {
+        capabilities.retain(|x| *x != Capability::Fps);
+    }
+
+    return Ok(capabilities);
 }
 
 pub unsafe fn ds2_script_start(process: &mut Process) {
@@ -172,13 +205,11 @@ pub unsafe fn ds2_frame_start(process: &mut Process) {}
 
 pub unsafe fn ds2_frame_end(process: &mut Process) {
     let pointers = POINTERS.as_ref().unwrap();
+}
 
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
+pub unsafe fn ds2_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
 }
 
 pub unsafe fn ds2_action_fps(process: &mut Process, fps: f32) {
@@ -219,18 +250,25 @@ pub unsafe fn ds2_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn ds2_flag_position(process: &mut Process, x: f32, y: f32, z: f32, range: f32) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds2_flag_loading(process: &mut Process) -> bool {
+    // Anything between the main menu and being ingame is a load
+    let pointers = POINTERS.as_ref().unwrap();
+    let game_state = pointers.game_state.read_i32_rel(None);
+    if game_state != 10 && game_state != 30 {
+        return true;
+    } else {
+        return false;
+    }
 }
 
-pub unsafe fn ds2_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds2_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
 }
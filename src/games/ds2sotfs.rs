@@ -1,10 +1,13 @@
 use mem_rs::prelude::*;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -16,116 +19,159 @@ struct GamePointers {
     game_state: Pointer,
     cutscene_3d: Pointer,
     cutscene_movie: Pointer,
+    position: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
 
-pub unsafe fn ds2sotfs_init(process: &mut Process) -> GameFuncs {
+pub struct DarkSouls2Sotfs {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl DarkSouls2Sotfs {
+    pub unsafe fn new(mut process: Process) -> Result<DarkSouls2Sotfs, SoulsTasError> {
+        let capabilities = ds2sotfs_init(&mut process)?;
+        return Ok(DarkSouls2Sotfs {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for DarkSouls2Sotfs {
+    fn name(&self) -> &'static str {
+        return GameType::DarkSouls2Sotfs.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { ds2sotfs_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { ds2sotfs_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { ds2sotfs_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { ds2sotfs_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { ds2sotfs_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { ds2sotfs_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { ds2sotfs_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2sotfs_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2sotfs_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2sotfs_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(ds2sotfs_flag_loading(&mut self.process)) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { ds2sotfs_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { ds2sotfs_read_position(&mut self.process) };
+    }
+}
+
+pub unsafe fn ds2sotfs_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "DS2SOTFS_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::DarkSouls2Sotfs);
     POINTERS = Some(GamePointers {
         fps_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_FPS_PATCH_ENABLED")
-                .expect("Couldn't find DS2SOTFS_FPS_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_FPS_PATCH_ENABLED")?,
             vec![0],
         ),
         fps_limit: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_FPS_CUSTOM_LIMIT")
-                .expect("Couldn't find DS2SOTFS_FPS_CUSTOM_LIMIT")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_FPS_CUSTOM_LIMIT")?,
             vec![0],
         ),
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find DS2SOTFS_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_FRAME_RUNNING")
-                .expect("Couldn't find DS2SOTFS_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_FRAME_RUNNING")?,
             vec![0],
         ),
         xinput_patch: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_XINPUT_PATCH_ENABLED")
-                .expect("Couldn't find DS2SOTFS_XINPUT_PATCH_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_XINPUT_PATCH_ENABLED")?,
             vec![0],
         ),
         xinput_state: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "DS2SOTFS_XINPUT_STATE")
-                .expect("Couldn't find DS2SOTFS_XINPUT_STATE")
-                .addr,
+            find_export(&soulstas_patches_exports, "DS2SOTFS_XINPUT_STATE")?,
             vec![0],
         ),
-        game_state: process
-            .scan_rel(
-                "game_state",
-                "48 8b 0d ? ? ? ? 48 8b 49 30 e8 ? ? ? ? 48 8b cb 48 83 c4 20 5b",
-                3,
-                7,
-                vec![0, 0x24ac],
-            )
-            .expect("Couldn't find game_state pointer"),
-        cutscene_3d: process
-            .scan_rel(
-                "cutscene_3d",
-                "48 8b 0d ? ? ? ? e8 ? ? ? ? 48 89 6f 30",
-                3,
-                7,
-                vec![0, 0x1a8, 0x10, 0x48],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
-        cutscene_movie: process
-            .scan_rel(
-                "cutscene_movie",
-                "48 8b 0d ? ? ? ? 48 85 c9 74 3f 8b 44 24 20 89 41 30 8b 44 24 24 89 41 34",
-                3,
-                7,
-                vec![0, 0x8, 0x30, 0x38, 0x20, 0x168, 0x30],
-            )
-            .expect("Couldn't find cutscene_movie pointer"),
+        game_state: scan_signature(process, &signatures, "game_state")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_movie: scan_signature(process, &signatures, "cutscene_movie")?,
+        position: scan_optional_signature(process, &signatures, "position"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: ds2sotfs_script_start,
-        script_end: ds2sotfs_script_end,
-        frame_next: ds2sotfs_frame_next,
-        frame_start: ds2sotfs_frame_start,
-        frame_end: ds2sotfs_frame_end,
-        action_fps: ds2sotfs_action_fps,
-        flag_frame: ds2sotfs_flag_frame,
-        flag_ingame: ds2sotfs_flag_ingame,
-        flag_cutscene: ds2sotfs_flag_cutscene,
-        flag_mainmenu: ds2sotfs_flag_mainmenu,
-        flag_position: ds2sotfs_flag_position,
-        flag_position_alternative: ds2sotfs_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    let mut capabilities = found_capabilities(
+        GameType::DarkSouls2Sotfs,
+        &[(Capability::Position, &pointers.position)],
+    );
+
+    // Same for the FPS patch, soulstas-patches skips it if its scan failed
+    if !patch_hooked(process, &soulstas_patches_exports, "DS2SOTFS_FPS_HOOKED")? {
+        capabilities.retain(|x| *x != Capability::Fps);
+    }
+
+    return Ok(capabilities);
 }
 
 pub unsafe fn ds2sotfs_script_start(process: &mut Process) {
@@ -159,13 +205,11 @@ pub unsafe fn ds2sotfs_frame_start(process: &mut Process) {}
 
 pub unsafe fn ds2sotfs_frame_end(process: &mut Process) {
     let pointers = POINTERS.as_ref().unwrap();
+}
 
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
+pub unsafe fn ds2sotfs_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
 }
 
 pub unsafe fn ds2sotfs_action_fps(process: &mut Process, fps: f32) {
@@ -207,24 +251,25 @@ pub unsafe fn ds2sotfs_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn ds2sotfs_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds2sotfs_flag_loading(process: &mut Process) -> bool {
+    // Anything between the main menu and being ingame is a load
+    let pointers = POINTERS.as_ref().unwrap();
+    let game_state = pointers.game_state.read_i32_rel(None);
+    if game_state != 10 && game_state != 30 {
+        return true;
+    } else {
+        return false;
+    }
 }
 
-pub unsafe fn ds2sotfs_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn ds2sotfs_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
 }
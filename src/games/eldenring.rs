@@ -1,12 +1,14 @@
 use mem_rs::prelude::*;
-use std::path::PathBuf;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::geometry::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
-use crate::utils::version::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -18,10 +20,18 @@ struct GamePointers {
     input_state: Pointer,
     save_active: Pointer,
     cutscene_3d: Pointer,
+    screen_state: Option<Pointer>,
     gamepad_index: Pointer,
     gamepad_flags: Pointer,
     position: Pointer,
     position_alternative: Pointer,
+    event_flags: Option<Pointer>,
+    animation: Option<Pointer>,
+    area: Option<Pointer>,
+    hp: Option<Pointer>,
+    menu: Option<Pointer>,
+    dialog: Option<Pointer>,
+    heading: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
@@ -30,93 +40,203 @@ static mut POINTERS: Option<GamePointers> = None;
 static mut GAMEPAD_INDEX_ORIG: i32 = 0;
 static mut GAMEPAD_FLAGS_ORIG: u32 = 0;
 
-pub unsafe fn eldenring_init(process: &mut Process) -> GameFuncs {
+pub struct EldenRing {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl EldenRing {
+    pub unsafe fn new(mut process: Process) -> Result<EldenRing, SoulsTasError> {
+        let capabilities = eldenring_init(&mut process)?;
+        return Ok(EldenRing {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for EldenRing {
+    fn name(&self) -> &'static str {
+        return GameType::EldenRing.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { eldenring_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { eldenring_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { eldenring_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { eldenring_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { eldenring_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { eldenring_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { eldenring_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(eldenring_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(eldenring_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(eldenring_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { eldenring_flag_loading(&mut self.process) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { eldenring_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { eldenring_read_position(&mut self.process).ok_or(GameError::ReadFailed) };
+    }
+
+    fn read_position_alternative(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe {
+            eldenring_read_position_alternative(&mut self.process).ok_or(GameError::ReadFailed)
+        };
+    }
+
+    fn read_heading(&mut self) -> Result<f32, GameError> {
+        return unsafe { eldenring_read_heading(&mut self.process) };
+    }
+
+    fn flag_event(&mut self, id: u32) -> Result<bool, GameError> {
+        return unsafe { eldenring_flag_event(&mut self.process, id) };
+    }
+
+    fn read_animation(&mut self) -> Result<i32, GameError> {
+        return unsafe { eldenring_read_animation(&mut self.process) };
+    }
+
+    fn read_area(&mut self) -> Result<[u8; 4], GameError> {
+        return unsafe { eldenring_read_area(&mut self.process) };
+    }
+
+    fn flag_zero_hp(&mut self) -> Result<bool, GameError> {
+        return unsafe { eldenring_flag_zero_hp(&mut self.process) };
+    }
+
+    fn flag_menu(&mut self) -> Result<bool, GameError> {
+        return unsafe { eldenring_flag_menu(&mut self.process) };
+    }
+
+    fn flag_dialog(&mut self) -> Result<bool, GameError> {
+        return unsafe { eldenring_flag_dialog(&mut self.process) };
+    }
+}
+
+pub unsafe fn eldenring_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulmods_module = inject_soulmods(process);
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulmods_module = inject_soulmods(process)?;
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module.unwrap());
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
-
-    // Get version
-    let process_version = Version::from_file_version_info(PathBuf::from(process.get_path()));
-
-    // Determine playerins offset based depending on version
-    let playerins_offset: usize = if process_version
-        >= (Version {
-            major: 1,
-            minor: 7,
-            build: 0,
-            revision: 0,
-        }) {
-        // 1.07.0+
-        0x1E508
-    } else {
-        0x18468
-    };
-
-    // Determine position offset depending on version
-    let position_offset: usize = if process_version
-        <= (Version {
-            major: 1,
-            minor: 3,
-            build: 2,
-            revision: 0,
-        }) {
-        // Up to 1.03.2
-        0x6b8
-    } else if process_version
-        <= (Version {
-            major: 1,
-            minor: 7,
-            build: 0,
-            revision: 0,
-        })
-    {
-        // 1.04.0 - 1.07.0
-        0x6b0
-    } else {
-        0x6c0
-    };
+    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module);
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "ER_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::EldenRing);
     POINTERS = Some(GamePointers {
-        fps_patch: process.create_pointer(soulmods_exports.iter().find(|f| f.name == "ER_FPS_PATCH_ENABLED").expect("Couldn't find ER_FPS_PATCH_ENABLED").addr, vec![0]),
-        fps_limit: process.create_pointer(soulmods_exports.iter().find(|f| f.name == "ER_FPS_CUSTOM_LIMIT").expect("Couldn't find ER_FPS_CUSTOM_LIMIT").addr, vec![0]),
-        frame_advance: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "ER_FRAME_ADVANCE_ENABLED").expect("Couldn't find ER_FRAME_ADVANCE_ENABLED").addr, vec![0]),
-        frame_running: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "ER_FRAME_RUNNING").expect("Couldn't find ER_FRAME_RUNNING").addr, vec![0]),
-        xinput_patch: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "ER_XINPUT_PATCH_ENABLED").expect("Couldn't find ER_XINPUT_PATCH_ENABLED").addr, vec![0]),
-        xinput_state: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "ER_XINPUT_STATE").expect("Couldn't find ER_XINPUT_STATE").addr, vec![0]),
-        input_state: process.scan_rel("input_state", "48 8B 05 ? ? ? ? 48 85 C0 74 0F 48 39 88", 3, 7, vec![0, playerins_offset, 0x58, 0xe8]).expect("Couldn't find input_state pointer"),
-        save_active: process.scan_rel("save_active", "4c 8b 0d ? ? ? ? 0f b6 d8 49 8b 69 08 48 8d 8d b0 02 00 00", 3, 7, vec![0, 0x8, 0x8]).expect("Couldn't find save_active pointer"),
-        cutscene_3d: process.scan_rel("cutscene_3d", "48 8B 05 ? ? ? ? 48 85 C0 75 2E 48 8D 0D ? ? ? ? E8 ? ? ? ? 4C 8B C8 4C 8D 05 ? ? ? ? BA ? ? ? ? 48 8D 0D ? ? ? ? E8 ? ? ? ? 48 8B 05 ? ? ? ? 80 B8 ? ? ? ? 00 75 4F 48 8B 0D ? ? ? ? 48 85 C9 75 2E 48 8D 0D", 3, 7, vec![0, 0xE1]).expect("Couldn't find cutscene_3d pointer"),
-        gamepad_index: process.scan_rel("gamepad_index", "48 8b 1d ? ? ? ? 8b f2 48 8b f9 48 85 db 75 2e", 3, 7, vec![0, 0x18, 0x10, 0x894]).expect("Couldn't find gamepad_index pointer"),
-        gamepad_flags: process.scan_rel("gamepad_flags", "48 8b 1d ? ? ? ? 8b f2 48 8b f9 48 85 db 75 2e", 3, 7, vec![0, 0x18, 0x10, 0x90c]).expect("Couldn't find gamepad_flags pointer"),
-        position: process.scan_rel("position", "48 8B 05 ? ? ? ? 48 85 C0 74 0F 48 39 88", 3, 7, vec![0, playerins_offset, position_offset]).expect("Couldn't find position pointer"),
-        position_alternative: process.scan_rel("position_alternative", "48 8B 05 ? ? ? ? 48 85 C0 74 0F 48 39 88", 3, 7, vec![0, playerins_offset, 0x190, 0x68, 0x70]).expect("Couldn't find position_alternative pointer"),
+        fps_patch: process.create_pointer(
+            find_export(&soulmods_exports, "ER_FPS_PATCH_ENABLED")?,
+            vec![0],
+        ),
+        fps_limit: process.create_pointer(
+            find_export(&soulmods_exports, "ER_FPS_CUSTOM_LIMIT")?,
+            vec![0],
+        ),
+        frame_advance: process.create_pointer(
+            find_export(&soulstas_patches_exports, "ER_FRAME_ADVANCE_ENABLED")?,
+            vec![0],
+        ),
+        frame_running: process.create_pointer(
+            find_export(&soulstas_patches_exports, "ER_FRAME_RUNNING")?,
+            vec![0],
+        ),
+        xinput_patch: process.create_pointer(
+            find_export(&soulstas_patches_exports, "ER_XINPUT_PATCH_ENABLED")?,
+            vec![0],
+        ),
+        xinput_state: process.create_pointer(
+            find_export(&soulstas_patches_exports, "ER_XINPUT_STATE")?,
+            vec![0],
+        ),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        screen_state: scan_optional_signature(process, &signatures, "screen_state"),
+        gamepad_index: scan_signature(process, &signatures, "gamepad_index")?,
+        gamepad_flags: scan_signature(process, &signatures, "gamepad_flags")?,
+        position: scan_signature(process, &signatures, "position")?,
+        position_alternative: scan_signature(process, &signatures, "position_alternative")?,
+        event_flags: scan_optional_signature(process, &signatures, "event_flags"),
+        animation: scan_optional_signature(process, &signatures, "animation"),
+        area: scan_optional_signature(process, &signatures, "area"),
+        hp: scan_optional_signature(process, &signatures, "hp"),
+        menu: scan_optional_signature(process, &signatures, "menu"),
+        dialog: scan_optional_signature(process, &signatures, "dialog"),
+        heading: scan_optional_signature(process, &signatures, "heading"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: eldenring_script_start,
-        script_end: eldenring_script_end,
-        frame_next: eldenring_frame_next,
-        frame_start: eldenring_frame_start,
-        frame_end: eldenring_frame_end,
-        action_fps: eldenring_action_fps,
-        flag_frame: eldenring_flag_frame,
-        flag_ingame: eldenring_flag_ingame,
-        flag_cutscene: eldenring_flag_cutscene,
-        flag_mainmenu: eldenring_flag_mainmenu,
-        flag_position: eldenring_flag_position,
-        flag_position_alternative: eldenring_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    return Ok(found_capabilities(
+        GameType::EldenRing,
+        &[
+            (Capability::Loading, &pointers.screen_state),
+            (Capability::EventFlags, &pointers.event_flags),
+            (Capability::Animation, &pointers.animation),
+            (Capability::Area, &pointers.area),
+            (Capability::Hp, &pointers.hp),
+            (Capability::Menu, &pointers.menu),
+            (Capability::Dialog, &pointers.dialog),
+            (Capability::Heading, &pointers.heading),
+        ],
+    ));
 }
 
 pub unsafe fn eldenring_script_start(process: &mut Process) {
@@ -164,13 +284,11 @@ pub unsafe fn eldenring_frame_end(process: &mut Process) {
     // Set correct gamepad flags
     pointers.gamepad_index.write_i32_rel(None, 999);
     pointers.gamepad_flags.write_u32_rel(None, 795);
+}
 
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
+pub unsafe fn eldenring_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
 }
 
 pub unsafe fn eldenring_action_fps(process: &mut Process, fps: f32) {
@@ -213,13 +331,17 @@ pub unsafe fn eldenring_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn eldenring_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
+pub unsafe fn eldenring_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let screen_state_pointer = optional_pointer(&pointers.screen_state, Capability::Loading)?;
+    if screen_state_pointer.read_i32_rel(None) == 0 {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
+}
+
+pub unsafe fn eldenring_read_position(process: &mut Process) -> Option<[f32; 3]> {
     let pointers = POINTERS.as_ref().unwrap();
 
     let mut positions_buffer: [u8; 12] = [0; 12];
@@ -227,68 +349,172 @@ pub unsafe fn eldenring_flag_position(
         .position
         .read_memory_rel(None, &mut positions_buffer)
     {
-        return false;
+        return None;
     }
-    let positions: [f32; 3] = std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer);
 
-    if range == 0.0 && positions[0] == x && positions[1] == y && positions[2] == z {
-        return true;
+    return Some(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
+}
+
+pub unsafe fn eldenring_read_position_alternative(process: &mut Process) -> Option<[f32; 3]> {
+    let pointers = POINTERS.as_ref().unwrap();
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !pointers
+        .position_alternative
+        .read_memory_rel(None, &mut positions_buffer)
+    {
+        return None;
     }
 
-    let distance = ((positions[0] - x).powf(2.0)
-        + (positions[1] - y).powf(2.0)
-        + (positions[2] - z).powf(2.0))
-    .sqrt();
+    return Some(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
+}
 
-    // println!("X: {:.24}, Y: {:.24}, Z: {:.24}, Distance: {}", positions[0], positions[1], positions[2], distance);
+pub unsafe fn eldenring_read_heading(process: &mut Process) -> Result<f32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let heading_pointer = optional_pointer(&pointers.heading, Capability::Heading)?;
 
-    if range > 0.0 && distance <= range {
-        return true;
+    // Stored as a quaternion (X, Y, Z, W), only the rotation around Y matters
+    let mut rotation_buffer: [u8; 16] = [0; 16];
+    if !heading_pointer.read_memory_rel(None, &mut rotation_buffer) {
+        return Err(GameError::ReadFailed);
     }
+    let rotation: [f32; 4] = std::mem::transmute::<[u8; 16], [f32; 4]>(rotation_buffer);
 
-    if range < 0.0 && distance >= range.abs() {
-        return true;
-    }
+    let angle = (2.0 * (rotation[3] * rotation[1] + rotation[0] * rotation[2]))
+        .atan2(1.0 - 2.0 * (rotation[0] * rotation[0] + rotation[1] * rotation[1]));
 
-    return false;
+    return Ok(radians_to_heading(angle));
 }
 
-pub unsafe fn eldenring_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
+// Based on SoulSplitter's event flag reading
+pub unsafe fn eldenring_flag_event(process: &mut Process, id: u32) -> Result<bool, GameError> {
     let pointers = POINTERS.as_ref().unwrap();
+    let event_flags_pointer = optional_pointer(&pointers.event_flags, Capability::EventFlags)?;
 
-    let mut positions_buffer: [u8; 12] = [0; 12];
-    if !pointers
-        .position_alternative
-        .read_memory_rel(None, &mut positions_buffer)
+    let event_flag_man = event_flags_pointer.read_u64_rel(None) as usize;
+    if event_flag_man == 0 {
+        return Ok(false);
+    }
+
+    // Flags are grouped into blocks, which are stored in a tree sorted by block ID
+    let divisor = event_flags_pointer.read_u32_abs(event_flag_man + 0x1c);
+    if divisor == 0 {
+        return Ok(false);
+    }
+    let block_id = id / divisor;
+    let block_index = id % divisor;
+
+    let tree_root = event_flags_pointer.read_u64_abs(event_flag_man + 0x38) as usize;
+    let mut block_node = tree_root;
+    let mut current_node = event_flags_pointer.read_u64_abs(tree_root + 0x8) as usize;
+    while current_node != 0 && event_flags_pointer.read_u8_abs(current_node + 0x19) == 0 {
+        if event_flags_pointer.read_u32_abs(current_node + 0x20) < block_id {
+            current_node = event_flags_pointer.read_u64_abs(current_node + 0x10) as usize;
+        } else {
+            block_node = current_node;
+            current_node = event_flags_pointer.read_u64_abs(current_node) as usize;
+        }
+    }
+
+    if current_node == 0
+        || block_node == current_node
+        || block_id < event_flags_pointer.read_u32_abs(block_node + 0x20)
     {
-        return false;
+        return Ok(false);
     }
-    let positions: [f32; 3] = std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer);
 
-    if range == 0.0 && positions[0] == x && positions[1] == y && positions[2] == z {
-        return true;
+    // Blocks are either stored in one big allocation or have their own pointer
+    let block_address: usize = match event_flags_pointer.read_u32_abs(block_node + 0x28) {
+        1 => {
+            let block_size = event_flags_pointer.read_u32_abs(event_flag_man + 0x20) as usize;
+            let block_offset = event_flags_pointer.read_u32_abs(block_node + 0x30) as usize;
+            event_flags_pointer.read_u64_abs(event_flag_man + 0x28) as usize
+                + block_size * block_offset
+        }
+        2 => 0,
+        _ => event_flags_pointer.read_u64_abs(block_node + 0x30) as usize,
+    };
+
+    if block_address == 0 {
+        return Ok(false);
     }
 
-    let distance = ((positions[0] - x).powf(2.0)
-        + (positions[1] - y).powf(2.0)
-        + (positions[2] - z).powf(2.0))
-    .sqrt();
+    let flags = event_flags_pointer.read_u8_abs(block_address + (block_index >> 3) as usize);
+    let flag = flags >> (7 - (block_index & 7)) & 1 == 1;
 
-    // println!("X: {:.24}, Y: {:.24}, Z: {:.24}, Distance: {}", positions[0], positions[1], positions[2], distance);
+    return Ok(flag);
+}
 
-    if range > 0.0 && distance <= range {
-        return true;
+pub unsafe fn eldenring_read_animation(process: &mut Process) -> Result<i32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let animation_pointer = optional_pointer(&pointers.animation, Capability::Animation)?;
+
+    let mut animation_buffer: [u8; 4] = [0; 4];
+    if !animation_pointer.read_memory_rel(None, &mut animation_buffer) {
+        return Err(GameError::ReadFailed);
     }
 
-    if range < 0.0 && distance >= range.abs() {
-        return true;
+    return Ok(i32::from_ne_bytes(animation_buffer));
+}
+
+pub unsafe fn eldenring_flag_zero_hp(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let hp_pointer = optional_pointer(&pointers.hp, Capability::Hp)?;
+
+    // No character loaded, so no HP either
+    if pointers.save_active.read_i32_rel(None) == -1 {
+        return Ok(false);
+    }
+
+    let mut hp_buffer: [u8; 4] = [0; 4];
+    if !hp_pointer.read_memory_rel(None, &mut hp_buffer) {
+        return Ok(false);
+    }
+
+    let hp = i32::from_ne_bytes(hp_buffer);
+    return Ok(hp <= 0);
+}
+
+pub unsafe fn eldenring_flag_menu(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let menu_pointer = optional_pointer(&pointers.menu, Capability::Menu)?;
+
+    // Number of open menu windows, the HUD doesn't count
+    let mut menu_buffer: [u8; 4] = [0; 4];
+    if !menu_pointer.read_memory_rel(None, &mut menu_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(i32::from_ne_bytes(menu_buffer) > 0);
+}
+
+pub unsafe fn eldenring_flag_dialog(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let dialog_pointer = optional_pointer(&pointers.dialog, Capability::Dialog)?;
+
+    // The open popup or prompt, null while there is none
+    let mut dialog_buffer: [u8; 8] = [0; 8];
+    if !dialog_pointer.read_memory_rel(None, &mut dialog_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(u64::from_ne_bytes(dialog_buffer) != 0);
+}
+
+pub unsafe fn eldenring_read_area(process: &mut Process) -> Result<[u8; 4], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let area_pointer = optional_pointer(&pointers.area, Capability::Area)?;
+
+    let mut area_buffer: [u8; 4] = [0; 4];
+    if !area_pointer.read_memory_rel(None, &mut area_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    // Stored as 0xAABBCCDD, -1 while not in any map
+    let area = u32::from_ne_bytes(area_buffer);
+    if area == u32::MAX {
+        return Err(GameError::ReadFailed);
     }
 
-    return false;
+    return Ok(area.to_be_bytes());
 }
@@ -0,0 +1,112 @@
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mem_rs::prelude::*;
+
+use crate::error::*;
+use crate::games::registry::*;
+use crate::games::shared::*;
+use crate::utils::abort::*;
+use crate::utils::mem::*;
+
+// Starting the game from the TAS tool, and waiting until it can be attached to
+
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(180);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MODULES_STABLE_POLLS: u32 = 4; // Polls without new modules before the game counts as loaded
+
+// Starts the game from an executable path or a Steam app ID
+pub fn launch_game(target: &str, args: &[String]) -> Result<(), SoulsTasError> {
+    let result = if target.parse::<u32>().is_ok() && !Path::new(target).exists() {
+        // Steam passes the arguments on to the game
+        let url = format!(
+            "steam://run/{}//{}/",
+            target,
+            args.join(" ").replace(' ', "%20")
+        );
+        Command::new("cmd").args(["/C", "start", "", &url]).spawn()
+    } else {
+        let mut command = Command::new(target);
+        command.args(args);
+        if let Some(dir) = Path::new(target).parent().filter(|x| x.is_dir()) {
+            command.current_dir(dir);
+        }
+        command.spawn()
+    };
+
+    if let Err(err) = result {
+        return Err(SoulsTasError::Attach(format!(
+            "Failed to launch {}: {}",
+            target, err
+        )));
+    }
+
+    return Ok(());
+}
+
+// Waits until the game is running, has its window open and no more modules are being loaded.
+// Attaching any earlier can make AoB scans fail, as the executable might still be unpacking.
+pub fn wait_for_game(info: &GameInfo) -> Result<Process, SoulsTasError> {
+    let start = Instant::now();
+
+    let mut process: Option<Process> = None;
+    let mut module_count: usize = 0;
+    let mut stable_polls: u32 = 0;
+
+    loop {
+        if abort_requested() {
+            return Err(SoulsTasError::Runtime(GameError::Aborted));
+        }
+        if start.elapsed() > LAUNCH_TIMEOUT {
+            return Err(SoulsTasError::Attach(format!(
+                "{} didn't start within {} seconds.",
+                info.name,
+                LAUNCH_TIMEOUT.as_secs()
+            )));
+        }
+
+        match process.as_mut() {
+            None => {
+                // Only attach once the window is open, so the main module is unpacked
+                let running_game = find_running_games()
+                    .into_iter()
+                    .find(|x| x.info.game_type == info.game_type);
+
+                if let Some(running_game) = running_game {
+                    if unsafe { has_main_window(running_game.process_id) } {
+                        let mut new_process =
+                            Process::new_with_id(info.process_name, running_game.process_id);
+                        if new_process.refresh().is_ok() {
+                            process = Some(new_process);
+                        }
+                    }
+                }
+            }
+            Some(attached_process) => {
+                // The game closed again, like Steam restarting it
+                if attached_process.refresh().is_err() {
+                    process = None;
+                    module_count = 0;
+                    stable_polls = 0;
+                    continue;
+                }
+
+                let new_module_count = attached_process.get_modules().len();
+                if new_module_count == module_count {
+                    stable_polls += 1;
+                } else {
+                    module_count = new_module_count;
+                    stable_polls = 0;
+                }
+
+                if stable_polls >= MODULES_STABLE_POLLS {
+                    return Ok(process.unwrap());
+                }
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
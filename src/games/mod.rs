@@ -1,57 +1,80 @@
-pub(crate) mod shared;
+pub mod registry;
+pub mod save;
+pub mod shared;
 
-#[cfg(target_arch = "x86")]
-pub(crate) mod ds1;
+#[cfg(windows)]
+pub mod launch;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod ds1r;
+#[cfg(windows)]
+pub mod process;
 
-#[cfg(target_arch = "x86")]
-pub(crate) mod ds2;
+pub mod sim;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod ds2sotfs;
+// Shared with soulstas-patches and soulstas-sigcheck
+pub use soulstas_shared::{game_type, signatures};
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod ds3;
+#[cfg(all(windows, target_arch = "x86"))]
+pub mod ds1;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod sekiro;
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod ds1r;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod eldenring;
+#[cfg(all(windows, target_arch = "x86"))]
+pub mod ds2;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod armoredcore6;
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod ds2sotfs;
 
-#[cfg(target_arch = "x86_64")]
-pub(crate) mod nightreign;
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod ds3;
 
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod sekiro;
+
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod eldenring;
+
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod armoredcore6;
+
+#[cfg(all(windows, target_arch = "x86_64"))]
+pub mod nightreign;
+
+pub use game_type::*;
+pub use registry::*;
+pub use save::*;
 pub use shared::*;
+pub use signatures::*;
+
+#[cfg(windows)]
+pub use launch::*;
+
+#[cfg(windows)]
+pub use process::*;
 
-#[cfg(target_arch = "x86")]
+#[cfg(all(windows, target_arch = "x86"))]
 pub use ds1::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use ds1r::*;
 
-#[cfg(target_arch = "x86")]
+#[cfg(all(windows, target_arch = "x86"))]
 pub use ds2::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use ds2sotfs::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use ds3::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use sekiro::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use eldenring::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use armoredcore6::*;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(windows, target_arch = "x86_64"))]
 pub use nightreign::*;
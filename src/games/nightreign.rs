@@ -1,8 +1,13 @@
 use mem_rs::prelude::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -12,102 +17,152 @@ struct GamePointers {
     input_state: Pointer,
     save_active: Pointer,
     cutscene_3d: Pointer,
+    screen_state: Option<Pointer>,
+    position: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
 
-pub unsafe fn nightreign_init(process: &mut Process) -> GameFuncs {
+pub struct NightReign {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl NightReign {
+    pub unsafe fn new(mut process: Process) -> Result<NightReign, SoulsTasError> {
+        let capabilities = nightreign_init(&mut process)?;
+        return Ok(NightReign {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for NightReign {
+    fn name(&self) -> &'static str {
+        return GameType::NightReign.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        // No gamepad support, keyboard and mouse only
+        return Box::new(SendInputSink);
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { nightreign_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { nightreign_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { nightreign_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { nightreign_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { nightreign_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { nightreign_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(nightreign_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(nightreign_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(nightreign_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { nightreign_flag_loading(&mut self.process) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { nightreign_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { nightreign_read_position(&mut self.process) };
+    }
+}
+
+pub unsafe fn nightreign_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulmods_module = inject_soulmods(process);
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulmods_module = inject_soulmods(process)?;
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module.unwrap());
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
+    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module);
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "NR_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::NightReign);
     POINTERS = Some(GamePointers {
         fps_patch: process.create_pointer(
-            soulmods_exports
-                .iter()
-                .find(|f| f.name == "NR_FPS_PATCH_ENABLED")
-                .expect("Couldn't find NR_FPS_PATCH_ENABLED")
-                .addr,
+            find_export(&soulmods_exports, "NR_FPS_PATCH_ENABLED")?,
             vec![0],
         ),
         fps_limit: process.create_pointer(
-            soulmods_exports
-                .iter()
-                .find(|f| f.name == "NR_FPS_CUSTOM_LIMIT")
-                .expect("Couldn't find NR_FPS_CUSTOM_LIMIT")
-                .addr,
+            find_export(&soulmods_exports, "NR_FPS_CUSTOM_LIMIT")?,
             vec![0],
         ),
         frame_advance: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "NR_FRAME_ADVANCE_ENABLED")
-                .expect("Couldn't find NR_FRAME_ADVANCE_ENABLED")
-                .addr,
+            find_export(&soulstas_patches_exports, "NR_FRAME_ADVANCE_ENABLED")?,
             vec![0],
         ),
         frame_running: process.create_pointer(
-            soulstas_patches_exports
-                .iter()
-                .find(|f| f.name == "NR_FRAME_RUNNING")
-                .expect("Couldn't find NR_FRAME_RUNNING")
-                .addr,
+            find_export(&soulstas_patches_exports, "NR_FRAME_RUNNING")?,
             vec![0],
         ),
-        input_state: process
-            .scan_rel(
-                "input_state",
-                "48 8B 05 ? ? ? ? 48 85 C0 74 0C 48 39 88",
-                3,
-                7,
-                vec![0, 0x174e8, 0x60, 0xf0],
-            )
-            .expect("Couldn't find input_state pointer"),
-        save_active: process
-            .scan_rel(
-                "save_active",
-                "48 8b 05 ? ? ? ? c6 84 07 02 01 00 00 00 48",
-                3,
-                7,
-                vec![0, 0x8, 0x78],
-            )
-            .expect("Couldn't find save_active pointer"),
-        cutscene_3d: process
-            .scan_rel(
-                "cutscene_3d",
-                "48 8b 0d ? ? ? ? 48 8b 49 58 48 85 c9 74 0a",
-                3,
-                7,
-                vec![0, 0xf1],
-            )
-            .expect("Couldn't find cutscene_3d pointer"),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        screen_state: scan_optional_signature(process, &signatures, "screen_state"),
+        position: scan_optional_signature(process, &signatures, "position"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: nightreign_script_start,
-        script_end: nightreign_script_end,
-        frame_next: nightreign_frame_next,
-        frame_start: nightreign_frame_start,
-        frame_end: nightreign_frame_end,
-        action_fps: nightreign_action_fps,
-        flag_frame: nightreign_flag_frame,
-        flag_ingame: nightreign_flag_ingame,
-        flag_cutscene: nightreign_flag_cutscene,
-        flag_mainmenu: nightreign_flag_mainmenu,
-        flag_position: nightreign_flag_position,
-        flag_position_alternative: nightreign_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    return Ok(found_capabilities(
+        GameType::NightReign,
+        &[
+            (Capability::Loading, &pointers.screen_state),
+            (Capability::Position, &pointers.position),
+        ],
+    ));
 }
 
 pub unsafe fn nightreign_script_start(process: &mut Process) {
@@ -177,24 +232,24 @@ pub unsafe fn nightreign_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn nightreign_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn nightreign_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let screen_state_pointer = optional_pointer(&pointers.screen_state, Capability::Loading)?;
+    if screen_state_pointer.read_i32_rel(None) == 0 {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
 }
 
-pub unsafe fn nightreign_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn nightreign_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
 }
@@ -0,0 +1,257 @@
+use mem_rs::prelude::*;
+use std::path::{Path, PathBuf};
+use std::{thread, time::Duration};
+
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::error::*;
+use crate::games::registry::*;
+use crate::games::shared::*;
+use crate::games::signatures::*;
+use crate::utils::mem::*;
+use crate::utils::version::*;
+
+// Attaching to and injecting into the game processes, only on Windows
+
+// Checks if the window of a process is the one in the foreground
+pub unsafe fn process_has_focus(process: &Process) -> bool {
+    return GetForegroundWindow() == get_hwnd_by_id(process.get_id());
+}
+
+// Refreshes the process, for when the game has to still be running
+pub fn refresh_process(process: &mut Process) -> Result<(), SoulsTasError> {
+    return process.refresh().map_err(|err| {
+        SoulsTasError::Attach(format!(
+            "Couldn't access {}: {}",
+            process_module_name(process),
+            err
+        ))
+    });
+}
+
+// Name of the executable, like eldenring.exe
+pub fn process_module_name(process: &Process) -> String {
+    let path = process.get_path();
+    return match Path::new(&path).file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => path,
+    };
+}
+
+// Address of a DLL export
+pub fn find_export(exports: &Vec<ModuleExport>, name: &str) -> Result<usize, SoulsTasError> {
+    return match exports.iter().find(|f| f.name == name) {
+        Some(export) => Ok(export.addr),
+        None => Err(SoulsTasError::Injection(format!(
+            "DLL export `{}` not found, the DLLs might be outdated",
+            name
+        ))),
+    };
+}
+
+// All signatures of the game, for the version that is running
+pub fn process_signatures(process: &Process, game_type: GameType) -> Vec<Signature> {
+    let version = Version::from_file_version_info(PathBuf::from(process.get_path()));
+    return game_signatures(game_type, &version);
+}
+
+// AoB scan of a signature by name, names the pointer and game version if it's missing
+pub fn scan_signature(
+    process: &Process,
+    signatures: &[Signature],
+    name: &str,
+) -> Result<Pointer, SoulsTasError> {
+    let signature = find_signature(signatures, name).ok_or(signature_error(process, name))?;
+
+    let result = match signature.scan_type {
+        ScanType::Relative { instruction_size } => process.scan_rel(
+            name,
+            &signature.pattern,
+            signature.scan_offset,
+            instruction_size,
+            signature.offsets.clone(),
+        ),
+        ScanType::Absolute => process.scan_abs(
+            name,
+            &signature.pattern,
+            signature.scan_offset,
+            signature.offsets.clone(),
+        ),
+    };
+
+    return result.map_err(|_| signature_error(process, name));
+}
+
+// AoB scan of a signature only some features need, the game still works without them if it's missing
+pub fn scan_optional_signature(
+    process: &Process,
+    signatures: &[Signature],
+    name: &str,
+) -> Option<Pointer> {
+    return scan_signature(process, signatures, name).ok();
+}
+
+// Pointer of an optional signature, the feature it's for is unsupported if it wasn't found
+pub fn optional_pointer(
+    pointer: &Option<Pointer>,
+    capability: Capability,
+) -> Result<&Pointer, GameError> {
+    return pointer.as_ref().ok_or(GameError::Unsupported(capability));
+}
+
+// Capabilities of the game, without the ones an optional pointer is missing for
+pub fn found_capabilities(
+    game_type: GameType,
+    optional: &[(Capability, &Option<Pointer>)],
+) -> Vec<Capability> {
+    return game_type
+        .info()
+        .capabilities
+        .iter()
+        .filter(|x| {
+            !optional
+                .iter()
+                .any(|(capability, pointer)| capability == *x && pointer.is_none())
+        })
+        .copied()
+        .collect();
+}
+
+// soulstas-patches skips a patch when its scan fails, this says if it's in
+pub unsafe fn patch_hooked(
+    process: &Process,
+    exports: &Vec<ModuleExport>,
+    export_name: &str,
+) -> Result<bool, SoulsTasError> {
+    let pointer = process.create_pointer(find_export(exports, export_name)?, vec![0]);
+    return Ok(pointer.read_bool_rel(None));
+}
+
+// The runner waits on frame advance every frame, so without its patch nothing can run
+pub unsafe fn require_patch(
+    process: &Process,
+    exports: &Vec<ModuleExport>,
+    export_name: &str,
+    signature_name: &str,
+) -> Result<(), SoulsTasError> {
+    if !patch_hooked(process, exports, export_name)? {
+        return Err(signature_error(process, signature_name));
+    }
+
+    return Ok(());
+}
+
+pub struct SignatureCheck {
+    pub signature: Signature,
+    pub address: Option<usize>, // Where the scan points to, None if the AoB wasn't found
+    pub resolved: Option<usize>, // End of the pointer chain, None if a pointer on the way is null
+}
+
+// Scans every signature of the game without changing anything, to see which ones broke after an update
+pub fn check_signatures(process: &Process, game_type: GameType) -> Vec<SignatureCheck> {
+    let mut checks: Vec<SignatureCheck> = Vec::new();
+
+    for signature in process_signatures(process, game_type) {
+        let pointer = scan_signature(process, std::slice::from_ref(&signature), &signature.name);
+        let address = pointer.as_ref().ok().map(|x| x.get_base_address());
+        let resolved = pointer.ok().and_then(|x| resolve_pointer(process, &x));
+
+        checks.push(SignatureCheck {
+            signature: signature,
+            address: address,
+            resolved: resolved,
+        });
+    }
+
+    return checks;
+}
+
+// Follows a pointer chain the same way mem-rs does when reading
+fn resolve_pointer(process: &Process, pointer: &Pointer) -> Option<usize> {
+    let pointer_size = if process.is_64_bit() { 8 } else { 4 };
+    let mut address = pointer.get_base_address();
+
+    for (i, offset) in pointer.offsets.iter().enumerate() {
+        address += offset;
+        if i + 1 == pointer.offsets.len() {
+            break;
+        }
+
+        let mut buffer = [0u8; 8];
+        if !process.read_memory_abs(address, &mut buffer[..pointer_size]) {
+            return None;
+        }
+        address = u64::from_le_bytes(buffer) as usize;
+        if address == 0 {
+            return None;
+        }
+    }
+
+    return Some(address);
+}
+
+fn signature_error(process: &Process, name: &str) -> SoulsTasError {
+    let version = Version::from_file_version_info(PathBuf::from(process.get_path()));
+    return SoulsTasError::Signature {
+        name: name.to_string(),
+        module: process_module_name(process),
+        version: version.to_string(),
+    };
+}
+
+unsafe fn inject_module(
+    process: &mut Process,
+    module_name: &str,
+) -> Result<ProcessModule, SoulsTasError> {
+    // Refresh process
+    refresh_process(process)?;
+
+    // Get/Inject module
+    return get_or_inject_module(process, module_name).ok_or(SoulsTasError::Injection(format!(
+        "Couldn't inject {}, make sure it's in {}",
+        module_name,
+        get_module_dir().display()
+    )));
+}
+
+unsafe fn wait_for_initialized(
+    process: &mut Process,
+    module: &ProcessModule,
+    export_name: &str,
+) -> Result<(), SoulsTasError> {
+    // Get exports
+    let exports: Vec<ModuleExport> = get_exports(module.clone());
+
+    // Get value to check if DLLs are initialized..
+    let ptr_initialized = process.create_pointer(find_export(&exports, export_name)?, vec![0]);
+
+    // ..and wait until they are
+    while !ptr_initialized.read_bool_rel(None) {
+        thread::sleep(Duration::from_micros(10));
+    }
+
+    return Ok(());
+}
+
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn inject_soulmods(process: &mut Process) -> Result<ProcessModule, SoulsTasError> {
+    let soulmods_module = inject_module(process, "soulmods_x64.dll")?;
+    wait_for_initialized(process, &soulmods_module, "SOULMODS_INITIALIZED")?;
+    return Ok(soulmods_module);
+}
+
+pub unsafe fn inject_soulstas_patches(
+    process: &mut Process,
+) -> Result<ProcessModule, SoulsTasError> {
+    #[cfg(target_arch = "x86_64")]
+    let soulstas_patches_module = inject_module(process, "soulstas_patches_x64.dll")?;
+    #[cfg(target_arch = "x86")]
+    let soulstas_patches_module = inject_module(process, "soulstas_patches_x86.dll")?;
+
+    wait_for_initialized(
+        process,
+        &soulstas_patches_module,
+        "SOULSTAS_PATCHES_INITIALIZED",
+    )?;
+    return Ok(soulstas_patches_module);
+}
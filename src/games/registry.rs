@@ -0,0 +1,383 @@
+#[cfg(windows)]
+use mem_rs::prelude::*;
+
+use crate::error::*;
+pub use crate::games::game_type::*;
+use crate::games::shared::*;
+use crate::games::sim::*;
+
+#[cfg(windows)]
+use crate::utils::mem::*;
+
+#[cfg(all(windows, target_arch = "x86"))]
+use crate::games::{ds1::*, ds2::*};
+
+#[cfg(all(windows, target_arch = "x86_64"))]
+use crate::games::{
+    armoredcore6::*, ds1r::*, ds2sotfs::*, ds3::*, eldenring::*, nightreign::*, sekiro::*,
+};
+
+// All supported games, used for picking one on the command line and for help texts
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X86,
+    X64,
+}
+
+impl Architecture {
+    // Architecture of the running TAS tool
+    pub fn current() -> Architecture {
+        #[cfg(target_arch = "x86")]
+        return Architecture::X86;
+        #[cfg(not(target_arch = "x86"))]
+        return Architecture::X64;
+    }
+
+    // Name of the TAS tool executable for this architecture
+    pub fn executable(&self) -> &'static str {
+        return match self {
+            Architecture::X86 => "soulstas_x86.exe",
+            Architecture::X64 => "soulstas_x64.exe",
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveBase {
+    AppData,
+    Documents,
+}
+
+// Saves are in (base)\(folder)\(account folder, usually the Steam ID)\(file)
+pub struct SaveLocation {
+    pub base: SaveBase,
+    pub folder: &'static str,
+    pub file: &'static str,
+}
+
+pub struct GameInfo {
+    pub game_type: GameType,
+    pub name: &'static str,
+    pub aliases: &'static [&'static str], // First one is the full name
+    pub process_name: &'static str,
+    pub architecture: Architecture,
+    pub capabilities: &'static [Capability],
+    pub warning: Option<&'static str>, // Printed before attaching
+    pub save_location: SaveLocation,
+}
+
+pub struct RunningGame {
+    pub info: &'static GameInfo,
+    pub process_id: u32,
+}
+
+pub const GAMES: &[GameInfo] = &[
+    GameInfo {
+        game_type: GameType::DarkSouls1,
+        name: "Dark Souls",
+        aliases: &["darksouls1", "ds1", "ptde"],
+        process_name: "DARKSOULS.exe",
+        architecture: Architecture::X86,
+        capabilities: &[
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+        ],
+        warning: None,
+        save_location: SaveLocation {
+            base: SaveBase::Documents,
+            folder: "NBGI\\DarkSouls",
+            file: "DRAKS0005.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::DarkSouls1Remastered,
+        name: "Dark Souls Remastered",
+        aliases: &["darksouls1remastered", "ds1r", "dsr"],
+        process_name: "DarkSoulsRemastered.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+            Capability::Heading,
+            Capability::EventFlags,
+            Capability::Animation,
+            Capability::Area,
+            Capability::Hp,
+        ],
+        warning: Some(
+            "DSR support might be spotty. Gamepad input is only supported if you have one plugged in.",
+        ),
+        save_location: SaveLocation {
+            base: SaveBase::Documents,
+            folder: "NBGI\\DARK SOULS REMASTERED",
+            file: "DRAKS0005.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::DarkSouls2,
+        name: "Dark Souls 2",
+        aliases: &["darksouls2", "ds2"],
+        process_name: "DarkSoulsII.exe",
+        architecture: Architecture::X86,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+        ],
+        warning: Some(
+            "DS2 support isn't great and not 100% consistent. Not ready for proper TASing.",
+        ),
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "DarkSoulsII",
+            file: "DARKSII0000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::DarkSouls2Sotfs,
+        name: "Dark Souls 2 (SOTFS)",
+        aliases: &["darksouls2sotfs", "ds2s", "sotfs"],
+        process_name: "DarkSoulsII.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+        ],
+        warning: Some(
+            "DS2 support isn't great and not 100% consistent. Not ready for proper TASing.",
+        ),
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "DarkSoulsII",
+            file: "DS2SOFS0000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::DarkSouls3,
+        name: "Dark Souls 3",
+        aliases: &["darksouls3", "ds3"],
+        process_name: "DarkSoulsIII.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+            Capability::Heading,
+            Capability::EventFlags,
+            Capability::Animation,
+            Capability::Area,
+            Capability::Hp,
+            Capability::Menu,
+            Capability::Dialog,
+        ],
+        warning: None,
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "DarkSoulsIII",
+            file: "DS30000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::Sekiro,
+        name: "Sekiro",
+        aliases: &["sekiro"],
+        process_name: "sekiro.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::CutsceneMovie,
+            Capability::Loading,
+            Capability::Position,
+            Capability::Heading,
+            Capability::EventFlags,
+            Capability::Animation,
+            Capability::Area,
+            Capability::Hp,
+            Capability::Menu,
+            Capability::Dialog,
+        ],
+        warning: None,
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "Sekiro",
+            file: "S0000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::EldenRing,
+        name: "Elden Ring",
+        aliases: &["eldenring", "er"],
+        process_name: "eldenring.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Gamepad,
+            Capability::Cutscene3d,
+            Capability::Loading,
+            Capability::Position,
+            Capability::PositionAlternative,
+            Capability::Heading,
+            Capability::EventFlags,
+            Capability::Animation,
+            Capability::Area,
+            Capability::Hp,
+            Capability::Menu,
+            Capability::Dialog,
+        ],
+        warning: None,
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "EldenRing",
+            file: "ER0000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::ArmoredCore6,
+        name: "Armored Core 6",
+        aliases: &["armoredcore6", "ac6"],
+        process_name: "armoredcore6.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Cutscene3d,
+            Capability::CutsceneBriefing,
+            Capability::Loading,
+            Capability::Position,
+        ],
+        warning: Some(
+            "AC6 support might be spotty. Gamepad input is not supported currently and cutscene actions are not 100% reliable.",
+        ),
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "ArmoredCore6",
+            file: "AC60000.sl2",
+        },
+    },
+    GameInfo {
+        game_type: GameType::NightReign,
+        name: "Nightreign",
+        aliases: &["nightreign", "nr"],
+        process_name: "nightreign.exe",
+        architecture: Architecture::X64,
+        capabilities: &[
+            Capability::Fps,
+            Capability::Cutscene3d,
+            Capability::Loading,
+            Capability::Position,
+        ],
+        warning: Some(
+            "Nightreign support might be spotty due to active game updates. Gamepad input is not supported currently.",
+        ),
+        save_location: SaveLocation {
+            base: SaveBase::AppData,
+            folder: "Nightreign",
+            file: "NR0000.sl2",
+        },
+    },
+];
+
+// GameType lives in soulstas-shared, which doesn't know about the rest of the game info
+pub trait GameTypeInfo {
+    fn info(&self) -> &'static GameInfo;
+}
+
+impl GameTypeInfo for GameType {
+    fn info(&self) -> &'static GameInfo {
+        return GAMES.iter().find(|x| x.game_type == *self).unwrap();
+    }
+}
+
+pub fn string_to_game(name: &str) -> Option<&'static GameInfo> {
+    let name = name.to_lowercase();
+    return GAMES.iter().find(|x| x.aliases.contains(&name.as_str()));
+}
+
+// Supported games that are running right now, of either architecture.
+// DS2 and SOTFS share an executable name, so the bitness of the process tells them apart.
+#[cfg(windows)]
+pub fn find_running_games() -> Vec<RunningGame> {
+    let mut running_games: Vec<RunningGame> = Vec::new();
+
+    for process in unsafe { get_running_processes() } {
+        let architecture = if process.is_32bit {
+            Architecture::X86
+        } else {
+            Architecture::X64
+        };
+
+        if let Some(info) = GAMES.iter().find(|x| {
+            x.process_name.eq_ignore_ascii_case(&process.name) && x.architecture == architecture
+        }) {
+            running_games.push(RunningGame {
+                info: info,
+                process_id: process.id,
+            });
+        }
+    }
+
+    return running_games;
+}
+
+// Whether a name is the simulated game instead of a real one
+pub fn is_simulation(name: &str) -> bool {
+    return matches!(name.to_lowercase().as_str(), "sim" | "simulation");
+}
+
+// Sets up an attached game, only games for the current architecture are available
+#[cfg(windows)]
+pub unsafe fn create_game(
+    game_type: GameType,
+    process: Process,
+) -> Result<Box<dyn Game>, SoulsTasError> {
+    #[cfg(target_arch = "x86_64")]
+    return match game_type {
+        GameType::DarkSouls1Remastered => Ok(Box::new(DarkSouls1Remastered::new(process)?)),
+        GameType::DarkSouls2Sotfs => Ok(Box::new(DarkSouls2Sotfs::new(process)?)),
+        GameType::DarkSouls3 => Ok(Box::new(DarkSouls3::new(process)?)),
+        GameType::Sekiro => Ok(Box::new(Sekiro::new(process)?)),
+        GameType::EldenRing => Ok(Box::new(EldenRing::new(process)?)),
+        GameType::ArmoredCore6 => Ok(Box::new(ArmoredCore6::new(process)?)),
+        GameType::NightReign => Ok(Box::new(NightReign::new(process)?)),
+        _ => Err(wrong_architecture(game_type)),
+    };
+
+    #[cfg(target_arch = "x86")]
+    return match game_type {
+        GameType::DarkSouls1 => Ok(Box::new(DarkSouls1::new(process)?)),
+        GameType::DarkSouls2 => Ok(Box::new(DarkSouls2::new(process)?)),
+        _ => Err(wrong_architecture(game_type)),
+    };
+}
+
+// Simulated game for trying out scripts without the actual game
+pub fn create_simulation() -> Box<dyn Game> {
+    return Box::new(SimGame::new());
+}
+
+pub fn wrong_architecture(game_type: GameType) -> SoulsTasError {
+    let info = game_type.info();
+    return SoulsTasError::Usage(format!(
+        "{} needs {}.",
+        info.name,
+        info.architecture.executable()
+    ));
+}
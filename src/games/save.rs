@@ -0,0 +1,238 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::*;
+use crate::games::registry::*;
+
+// Copies the save a TAS needs into the game's save folder, and puts the user's own save back afterwards
+
+pub struct SaveStaging {
+    save_path: PathBuf,
+    backup_path: Option<PathBuf>, // None if there was no save before
+    original_hash: Option<u64>,
+    finished: bool, // Restored, or left for the user to restore
+}
+
+impl SaveStaging {
+    // Has to happen while the game is closed, it only reads the save on startup
+    pub fn stage(
+        info: &GameInfo,
+        tas_save_path: &Path,
+        slot: Option<&str>,
+        save_dir: Option<&Path>,
+    ) -> Result<SaveStaging, SoulsTasError> {
+        if !tas_save_path.is_file() {
+            return Err(SoulsTasError::Save(format!(
+                "Can't find save file at {}",
+                tas_save_path.display()
+            )));
+        }
+
+        let save_path = find_save_folder(info, slot, save_dir)?.join(info.save_location.file);
+        let backup_path = backup_path(&save_path);
+
+        // Don't overwrite a backup that was never restored
+        if backup_path.exists() {
+            return Err(SoulsTasError::Save(format!(
+                "A save backup from an earlier run exists at {}, restore or delete it first.",
+                backup_path.display()
+            )));
+        }
+
+        // Back up the current save
+        let mut original_hash: Option<u64> = None;
+        if save_path.exists() {
+            original_hash = Some(hash_file(&save_path)?);
+            copy_file(&save_path, &backup_path)?;
+        }
+
+        let mut staging = SaveStaging {
+            save_path: save_path,
+            backup_path: if original_hash.is_some() {
+                Some(backup_path)
+            } else {
+                None
+            },
+            original_hash: original_hash,
+            finished: false,
+        };
+
+        // Copy the TAS save over it and make sure it's the same file
+        let tas_save_hash = hash_file(tas_save_path)?;
+        copy_file(tas_save_path, &staging.save_path)?;
+        if hash_file(&staging.save_path)? != tas_save_hash {
+            staging.restore()?;
+            return Err(SoulsTasError::Save(format!(
+                "Save file at {} doesn't match the TAS save after copying.",
+                staging.save_path.display()
+            )));
+        }
+
+        println!(
+            "Using save {} (hash {:016x})",
+            tas_save_path.display(),
+            tas_save_hash
+        );
+        return Ok(staging);
+    }
+
+    // Puts the original save back, or removes the TAS save if there was none
+    pub fn restore(&mut self) -> Result<(), SoulsTasError> {
+        if self.finished {
+            return Ok(());
+        }
+
+        match &self.backup_path {
+            Some(backup_path) => {
+                copy_file(backup_path, &self.save_path)?;
+                if Some(hash_file(&self.save_path)?) != self.original_hash {
+                    return Err(SoulsTasError::Save(format!(
+                        "Restored save doesn't match the original, the backup is kept at {}",
+                        backup_path.display()
+                    )));
+                }
+                let _ = fs::remove_file(backup_path);
+                println!("Restored the original save at {}", self.save_path.display());
+            }
+            None => {
+                let _ = fs::remove_file(&self.save_path);
+                println!("Removed the TAS save at {}", self.save_path.display());
+            }
+        }
+
+        self.finished = true;
+        return Ok(());
+    }
+
+    // Leaves the TAS save in place, for when the game might still write to it
+    pub fn keep(&mut self) -> SoulsTasError {
+        self.finished = true;
+        return SoulsTasError::Save(self.unrestored_message());
+    }
+
+    fn unrestored_message(&self) -> String {
+        return match &self.backup_path {
+            Some(backup_path) => format!(
+                "The original save wasn't restored, it's backed up at {}. Copy it back over {} once the game is closed.",
+                backup_path.display(),
+                self.save_path.display()
+            ),
+            None => format!(
+                "The TAS save at {} wasn't removed, delete it once the game is closed.",
+                self.save_path.display()
+            ),
+        };
+    }
+}
+
+// Restoring here could happen while the game still runs, and its next autosave would overwrite
+// the original again. Only say where the backup is.
+impl Drop for SaveStaging {
+    fn drop(&mut self) {
+        if !self.finished {
+            println!("Error: {}", self.unrestored_message());
+        }
+    }
+}
+
+// Account folder inside the game's save folder, picked by the slot if there are several.
+// save_dir replaces the game's save folder, for moved or non-Steam installs.
+fn find_save_folder(
+    info: &GameInfo,
+    slot: Option<&str>,
+    save_dir: Option<&Path>,
+) -> Result<PathBuf, SoulsTasError> {
+    let game_folder = match save_dir {
+        Some(x) => x.to_path_buf(),
+        None => default_save_folder(info)?,
+    };
+
+    if let Some(slot) = slot {
+        let folder = game_folder.join(slot);
+        if !folder.is_dir() {
+            return Err(SoulsTasError::Save(format!(
+                "Save folder {} doesn't exist",
+                folder.display()
+            )));
+        }
+        return Ok(folder);
+    }
+
+    let folders: Vec<PathBuf> = match fs::read_dir(&game_folder) {
+        Ok(x) => x
+            .filter_map(|x| x.ok())
+            .map(|x| x.path())
+            .filter(|x| x.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    return match folders.len() {
+        0 => Err(SoulsTasError::Save(format!(
+            "No save folder found in {}, start the game once first.",
+            game_folder.display()
+        ))),
+        1 => Ok(folders[0].clone()),
+        _ => Err(SoulsTasError::Save(format!(
+            "Several save folders found in {} ({}), pick one with the slot.",
+            game_folder.display(),
+            folders
+                .iter()
+                .filter_map(|x| x.file_name())
+                .map(|x| x.to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))),
+    };
+}
+
+// Where the game keeps its saves, by default
+fn default_save_folder(info: &GameInfo) -> Result<PathBuf, SoulsTasError> {
+    let base_var = match info.save_location.base {
+        SaveBase::AppData => "APPDATA",
+        SaveBase::Documents => "USERPROFILE",
+    };
+    let mut base = PathBuf::from(env::var(base_var).map_err(|_| {
+        SoulsTasError::Save(format!(
+            "Can't find the save folder, {} isn't set",
+            base_var
+        ))
+    })?);
+    if info.save_location.base == SaveBase::Documents {
+        base = base.join("Documents");
+    }
+
+    return Ok(base.join(info.save_location.folder));
+}
+
+fn backup_path(save_path: &Path) -> PathBuf {
+    let mut backup_name = save_path.file_name().unwrap().to_os_string();
+    backup_name.push(".soulstas-backup");
+    return save_path.with_file_name(backup_name);
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<(), SoulsTasError> {
+    return fs::copy(from, to).map(|_| ()).map_err(|err| {
+        SoulsTasError::Save(format!(
+            "Can't copy {} to {}: {}",
+            from.display(),
+            to.display(),
+            err
+        ))
+    });
+}
+
+// FNV-1a, only used to tell save files apart
+pub fn hash_file(path: &Path) -> Result<u64, SoulsTasError> {
+    let data = fs::read(path)
+        .map_err(|err| SoulsTasError::Save(format!("Can't read {}: {}", path.display(), err)))?;
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    return Ok(hash);
+}
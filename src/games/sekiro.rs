@@ -1,10 +1,14 @@
 use mem_rs::prelude::*;
-use windows::Win32::UI::Input::XboxController::*;
 
+use crate::error::*;
+use crate::games::process::*;
+use crate::games::registry::*;
 use crate::games::shared::*;
 
-use crate::utils::input::*;
+use crate::utils::geometry::*;
+use crate::utils::input_sink::*;
 use crate::utils::mem::*;
+use crate::utils::send_input::*;
 
 struct GamePointers {
     fps_patch: Pointer,
@@ -19,6 +23,16 @@ struct GamePointers {
     cutscene_movie: Pointer,
     gamepad_index: Pointer,
     gamepad_flags: Pointer,
+    event_flag_man: Option<Pointer>,
+    field_area: Option<Pointer>,
+    animation: Option<Pointer>,
+    area: Option<Pointer>,
+    hp: Option<Pointer>,
+    menu: Option<Pointer>,
+    dialog: Option<Pointer>,
+    loading: Option<Pointer>,
+    position: Option<Pointer>,
+    heading: Option<Pointer>,
 }
 
 static mut POINTERS: Option<GamePointers> = None;
@@ -27,51 +41,200 @@ static mut POINTERS: Option<GamePointers> = None;
 static mut GAMEPAD_INDEX_ORIG: i32 = 0;
 static mut GAMEPAD_FLAGS_ORIG: u32 = 0;
 
-pub unsafe fn sekiro_init(process: &mut Process) -> GameFuncs {
+pub struct Sekiro {
+    process: Process,
+    capabilities: Vec<Capability>,
+}
+
+impl Sekiro {
+    pub unsafe fn new(mut process: Process) -> Result<Sekiro, SoulsTasError> {
+        let capabilities = sekiro_init(&mut process)?;
+        return Ok(Sekiro {
+            process: process,
+            capabilities: capabilities,
+        });
+    }
+}
+
+impl Game for Sekiro {
+    fn name(&self) -> &'static str {
+        return GameType::Sekiro.info().name;
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return &self.capabilities;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        return unsafe { sekiro_input_sink() };
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return self.process.refresh().map_err(|_| GameError::ProcessLost);
+    }
+
+    fn script_start(&mut self) {
+        unsafe { sekiro_script_start(&mut self.process) }
+    }
+
+    fn script_end(&mut self) {
+        unsafe { sekiro_script_end(&mut self.process) }
+    }
+
+    fn frame_next(&mut self) {
+        unsafe { sekiro_frame_next(&mut self.process) }
+    }
+
+    fn frame_start(&mut self) {
+        unsafe { sekiro_frame_start(&mut self.process) }
+    }
+
+    fn frame_end(&mut self) {
+        unsafe { sekiro_frame_end(&mut self.process) }
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return unsafe { sekiro_flag_frame(&mut self.process) };
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(process_has_focus(&self.process)) };
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(sekiro_flag_ingame(&mut self.process)) };
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(sekiro_flag_cutscene(&mut self.process)) };
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return unsafe { Ok(sekiro_flag_mainmenu(&mut self.process)) };
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return unsafe { sekiro_flag_loading(&mut self.process) };
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        unsafe { sekiro_action_fps(&mut self.process, fps) };
+        return Ok(());
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return unsafe { sekiro_read_position(&mut self.process) };
+    }
+
+    fn read_heading(&mut self) -> Result<f32, GameError> {
+        return unsafe { sekiro_read_heading(&mut self.process) };
+    }
+
+    fn flag_event(&mut self, id: u32) -> Result<bool, GameError> {
+        return unsafe { sekiro_flag_event(&mut self.process, id) };
+    }
+
+    fn read_animation(&mut self) -> Result<i32, GameError> {
+        return unsafe { sekiro_read_animation(&mut self.process) };
+    }
+
+    fn read_area(&mut self) -> Result<[u8; 4], GameError> {
+        return unsafe { sekiro_read_area(&mut self.process) };
+    }
+
+    fn flag_zero_hp(&mut self) -> Result<bool, GameError> {
+        return unsafe { sekiro_flag_zero_hp(&mut self.process) };
+    }
+
+    fn flag_menu(&mut self) -> Result<bool, GameError> {
+        return unsafe { sekiro_flag_menu(&mut self.process) };
+    }
+
+    fn flag_dialog(&mut self) -> Result<bool, GameError> {
+        return unsafe { sekiro_flag_dialog(&mut self.process) };
+    }
+}
+
+pub unsafe fn sekiro_init(process: &mut Process) -> Result<Vec<Capability>, SoulsTasError> {
     // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    refresh_process(process)?;
 
     // Inject DLLs
-    let soulmods_module = inject_soulmods(process);
-    let soulstas_patches_module = inject_soulstas_patches(process);
+    let soulmods_module = inject_soulmods(process)?;
+    let soulstas_patches_module = inject_soulstas_patches(process)?;
 
     // Get exports
-    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module.unwrap());
-    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module.unwrap());
+    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module);
+    let soulstas_patches_exports: Vec<ModuleExport> = get_exports(soulstas_patches_module);
+    require_patch(
+        process,
+        &soulstas_patches_exports,
+        "SEKIRO_FRAME_ADVANCE_HOOKED",
+        "frame_advance",
+    )?;
 
     // Get all necessary memory pointers
+    let signatures = process_signatures(process, GameType::Sekiro);
     POINTERS = Some(GamePointers {
-        fps_patch: process.create_pointer(soulmods_exports.iter().find(|f| f.name == "SEKIRO_FPS_PATCH_ENABLED").expect("Couldn't find SEKIRO_FPS_PATCH_ENABLED").addr, vec![0]),
-        fps_limit: process.create_pointer(soulmods_exports.iter().find(|f| f.name == "SEKIRO_FPS_CUSTOM_LIMIT").expect("Couldn't find SEKIRO_FPS_CUSTOM_LIMIT").addr, vec![0]),
-        frame_advance: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "SEKIRO_FRAME_ADVANCE_ENABLED").expect("Couldn't find SEKIRO_FRAME_ADVANCE_ENABLED").addr, vec![0]),
-        frame_running: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "SEKIRO_FRAME_RUNNING").expect("Couldn't find SEKIRO_FRAME_RUNNING").addr, vec![0]),
-        xinput_patch: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "SEKIRO_XINPUT_PATCH_ENABLED").expect("Couldn't find SEKIRO_XINPUT_PATCH_ENABLED").addr, vec![0]),
-        xinput_state: process.create_pointer(soulstas_patches_exports.iter().find(|f| f.name == "SEKIRO_XINPUT_STATE").expect("Couldn't find SEKIRO_XINPUT_STATE").addr, vec![0]),
-        input_state: process.scan_rel("input_state", "48 8B 35 ? ? ? ? 44 0F 28 18", 3, 7, vec![0, 0x88, 0x50, 0x190]).expect("Couldn't find input_state pointer"),
-        save_active: process.scan_rel("save_active", "48 8b 15 ? ? ? ? 8b 44 24 28 f3 0f 10 44 24 30", 3, 7, vec![0, 0xbf4]).expect("Couldn't find save_active pointer"),
-        cutscene_3d: process.scan_rel("cutscene_3d", "48 8b 05 ? ? ? ? 4c 8b f9 48 8b 49 08", 3, 7, vec![0, 0xd4]).expect("Couldn't find cutscene_3d pointer"),
-        cutscene_movie: process.scan_rel("cutscene_movie", "80 bf b8 0a 00 00 00 75 3f 48 8b 0d ? ? ? ? 48 85 c9 75 2e 48 8d 0d ? ? ? ? e8 ? ? ? ? 4c 8b c8 4c 8d 05 ? ? ? ? ba b1 00 00 00", 12, 16, vec![0, 0x20]).expect("Couldn't find cutscene_movie pointer"),
-        gamepad_index: process.scan_rel("gamepad_index", "4c 8b 05 ? ? ? ? 48 8b f2 48 8b d9 4d 85 c0 75 2e", 3, 7, vec![0, 0x18, 0x10, 0x244]).expect("Couldn't find gamepad_index pointer"),
-        gamepad_flags: process.scan_rel("gamepad_flags", "4c 8b 05 ? ? ? ? 48 8b f2 48 8b d9 4d 85 c0 75 2e", 3, 7, vec![0, 0x18, 0x10, 0x2bc]).expect("Couldn't find gamepad_flags pointer"),
+        fps_patch: process.create_pointer(
+            find_export(&soulmods_exports, "SEKIRO_FPS_PATCH_ENABLED")?,
+            vec![0],
+        ),
+        fps_limit: process.create_pointer(
+            find_export(&soulmods_exports, "SEKIRO_FPS_CUSTOM_LIMIT")?,
+            vec![0],
+        ),
+        frame_advance: process.create_pointer(
+            find_export(&soulstas_patches_exports, "SEKIRO_FRAME_ADVANCE_ENABLED")?,
+            vec![0],
+        ),
+        frame_running: process.create_pointer(
+            find_export(&soulstas_patches_exports, "SEKIRO_FRAME_RUNNING")?,
+            vec![0],
+        ),
+        xinput_patch: process.create_pointer(
+            find_export(&soulstas_patches_exports, "SEKIRO_XINPUT_PATCH_ENABLED")?,
+            vec![0],
+        ),
+        xinput_state: process.create_pointer(
+            find_export(&soulstas_patches_exports, "SEKIRO_XINPUT_STATE")?,
+            vec![0],
+        ),
+        input_state: scan_signature(process, &signatures, "input_state")?,
+        save_active: scan_signature(process, &signatures, "save_active")?,
+        cutscene_3d: scan_signature(process, &signatures, "cutscene_3d")?,
+        cutscene_movie: scan_signature(process, &signatures, "cutscene_movie")?,
+        gamepad_index: scan_signature(process, &signatures, "gamepad_index")?,
+        gamepad_flags: scan_signature(process, &signatures, "gamepad_flags")?,
+        event_flag_man: scan_optional_signature(process, &signatures, "event_flag_man"),
+        field_area: scan_optional_signature(process, &signatures, "field_area"),
+        animation: scan_optional_signature(process, &signatures, "animation"),
+        area: scan_optional_signature(process, &signatures, "area"),
+        hp: scan_optional_signature(process, &signatures, "hp"),
+        menu: scan_optional_signature(process, &signatures, "menu"),
+        dialog: scan_optional_signature(process, &signatures, "dialog"),
+        loading: scan_optional_signature(process, &signatures, "loading"),
+        position: scan_optional_signature(process, &signatures, "position"),
+        heading: scan_optional_signature(process, &signatures, "heading"),
     });
 
-    // Return all functions
-    let game_funcs = GameFuncs {
-        script_start: sekiro_script_start,
-        script_end: sekiro_script_end,
-        frame_next: sekiro_frame_next,
-        frame_start: sekiro_frame_start,
-        frame_end: sekiro_frame_end,
-        action_fps: sekiro_action_fps,
-        flag_frame: sekiro_flag_frame,
-        flag_ingame: sekiro_flag_ingame,
-        flag_cutscene: sekiro_flag_cutscene,
-        flag_mainmenu: sekiro_flag_mainmenu,
-        flag_position: sekiro_flag_position,
-        flag_position_alternative: sekiro_flag_position_alternative,
-    };
-
-    return game_funcs;
+    // Leave out what an optional signature wasn't found for
+    let pointers = POINTERS.as_ref().unwrap();
+    return Ok(found_capabilities(
+        GameType::Sekiro,
+        &[
+            (Capability::Loading, &pointers.loading),
+            (Capability::EventFlags, &pointers.event_flag_man),
+            (Capability::EventFlags, &pointers.field_area),
+            (Capability::Animation, &pointers.animation),
+            (Capability::Area, &pointers.area),
+            (Capability::Hp, &pointers.hp),
+            (Capability::Menu, &pointers.menu),
+            (Capability::Dialog, &pointers.dialog),
+            (Capability::Position, &pointers.position),
+            (Capability::Heading, &pointers.heading),
+        ],
+    ));
 }
 
 pub unsafe fn sekiro_script_start(process: &mut Process) {
@@ -119,13 +282,11 @@ pub unsafe fn sekiro_frame_end(process: &mut Process) {
     // Set correct gamepad flags
     pointers.gamepad_index.write_i32_rel(None, 999);
     pointers.gamepad_flags.write_u32_rel(None, 795);
+}
 
-    // Send gamepad input
-    let xinput_state_override_buf = &*(&XINPUT_STATE_OVERRIDE as *const XINPUT_STATE
-        as *const [u8; core::mem::size_of::<XINPUT_STATE>()]);
-    pointers
-        .xinput_state
-        .write_memory_rel(None, xinput_state_override_buf);
+pub unsafe fn sekiro_input_sink() -> Box<dyn InputSink> {
+    let pointers = POINTERS.as_ref().unwrap();
+    return hook_input_sink(pointers.xinput_state.clone());
 }
 
 pub unsafe fn sekiro_action_fps(process: &mut Process, fps: f32) {
@@ -168,24 +329,193 @@ pub unsafe fn sekiro_flag_mainmenu(process: &mut Process) -> bool {
     }
 }
 
-pub unsafe fn sekiro_flag_position(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn sekiro_flag_loading(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let loading_pointer = optional_pointer(&pointers.loading, Capability::Loading)?;
+    if loading_pointer.read_bool_rel(None) {
+        return Ok(true);
+    } else {
+        return Ok(false);
+    }
 }
 
-pub unsafe fn sekiro_flag_position_alternative(
-    process: &mut Process,
-    x: f32,
-    y: f32,
-    z: f32,
-    range: f32,
-) -> bool {
-    // Not implemented
-    return true;
+pub unsafe fn sekiro_read_position(process: &mut Process) -> Result<[f32; 3], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let position_pointer = optional_pointer(&pointers.position, Capability::Position)?;
+
+    let mut positions_buffer: [u8; 12] = [0; 12];
+    if !position_pointer.read_memory_rel(None, &mut positions_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(std::mem::transmute::<[u8; 12], [f32; 3]>(positions_buffer));
+}
+
+pub unsafe fn sekiro_read_heading(process: &mut Process) -> Result<f32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let heading_pointer = optional_pointer(&pointers.heading, Capability::Heading)?;
+
+    let mut angle_buffer: [u8; 4] = [0; 4];
+    if !heading_pointer.read_memory_rel(None, &mut angle_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(radians_to_heading(f32::from_ne_bytes(angle_buffer)));
+}
+
+// Based on SoulSplitter's event flag reading
+pub unsafe fn sekiro_flag_event(process: &mut Process, id: u32) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let event_flag_man_pointer =
+        optional_pointer(&pointers.event_flag_man, Capability::EventFlags)?;
+    let field_area_pointer = optional_pointer(&pointers.field_area, Capability::EventFlags)?;
+
+    let group = (id / 10000000) % 10;
+    let area = (id / 100000) % 100;
+    let block = (id / 10000) % 10;
+    let section = (id / 1000) % 10;
+    let number = id % 1000;
+
+    // Find the world block category the flag belongs to, global flags don't have one
+    let mut category: i32 = -1;
+    if area >= 90 || area + block == 0 {
+        category = 0;
+    } else {
+        let field_area = field_area_pointer.read_u64_rel(None) as usize;
+        if field_area == 0 {
+            return Ok(false);
+        }
+
+        let world_info_owner = field_area_pointer.read_u64_abs(field_area + 0x18) as usize;
+        if world_info_owner == 0 {
+            return Ok(false);
+        }
+
+        let world_info_count = field_area_pointer.read_i32_abs(world_info_owner + 0x8);
+        let world_info_list = world_info_owner + 0x10;
+
+        'world_info: for world_info_index in 0..world_info_count.max(0) as usize {
+            let world_info = world_info_list + world_info_index * 0x38;
+            if field_area_pointer.read_u8_abs(world_info + 0xb) as u32 != area {
+                continue;
+            }
+
+            let block_count = field_area_pointer.read_u8_abs(world_info + 0x20) as usize;
+            let block_list = field_area_pointer.read_u64_abs(world_info + 0x28) as usize;
+            for block_index in 0..block_count {
+                let block_info = block_list + block_index * 0x70;
+                let block_id = field_area_pointer.read_u32_abs(block_info + 0x8);
+                if (block_id >> 16) & 0xff == block && block_id >> 24 == area {
+                    category = field_area_pointer.read_i32_abs(block_info + 0x20) + 1;
+                    break 'world_info;
+                }
+            }
+        }
+    }
+
+    if category < 0 {
+        return Ok(false);
+    }
+
+    let event_flag_man = event_flag_man_pointer.read_u64_rel(None) as usize;
+    if event_flag_man == 0 {
+        return Ok(false);
+    }
+
+    let group_list = event_flag_man_pointer.read_u64_abs(event_flag_man + 0x218) as usize;
+    if group_list == 0 {
+        return Ok(false);
+    }
+
+    let group_address =
+        event_flag_man_pointer.read_u64_abs(group_list + group as usize * 0x18) as usize;
+    if group_address == 0 {
+        return Ok(false);
+    }
+
+    let flags_address = event_flag_man_pointer
+        .read_u64_abs(group_address + ((section as usize) << 4) + category as usize * 0xa8)
+        as usize;
+    if flags_address == 0 {
+        return Ok(false);
+    }
+
+    let flags = event_flag_man_pointer.read_u32_abs(flags_address + (number >> 5) as usize * 4);
+    let flag = flags >> (31 - (number & 0x1f)) & 1 == 1;
+
+    return Ok(flag);
+}
+
+pub unsafe fn sekiro_read_animation(process: &mut Process) -> Result<i32, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let animation_pointer = optional_pointer(&pointers.animation, Capability::Animation)?;
+
+    let mut animation_buffer: [u8; 4] = [0; 4];
+    if !animation_pointer.read_memory_rel(None, &mut animation_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(i32::from_ne_bytes(animation_buffer));
+}
+
+pub unsafe fn sekiro_flag_zero_hp(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let hp_pointer = optional_pointer(&pointers.hp, Capability::Hp)?;
+
+    // No character loaded, so no HP either
+    if pointers.save_active.read_i32_rel(None) == -1 {
+        return Ok(false);
+    }
+
+    let mut hp_buffer: [u8; 4] = [0; 4];
+    if !hp_pointer.read_memory_rel(None, &mut hp_buffer) {
+        return Ok(false);
+    }
+
+    let hp = i32::from_ne_bytes(hp_buffer);
+    return Ok(hp <= 0);
+}
+
+pub unsafe fn sekiro_flag_menu(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let menu_pointer = optional_pointer(&pointers.menu, Capability::Menu)?;
+
+    // Number of open menu windows, the HUD doesn't count
+    let mut menu_buffer: [u8; 4] = [0; 4];
+    if !menu_pointer.read_memory_rel(None, &mut menu_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(i32::from_ne_bytes(menu_buffer) > 0);
+}
+
+pub unsafe fn sekiro_flag_dialog(process: &mut Process) -> Result<bool, GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let dialog_pointer = optional_pointer(&pointers.dialog, Capability::Dialog)?;
+
+    // The open popup or prompt, null while there is none
+    let mut dialog_buffer: [u8; 8] = [0; 8];
+    if !dialog_pointer.read_memory_rel(None, &mut dialog_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(u64::from_ne_bytes(dialog_buffer) != 0);
+}
+
+pub unsafe fn sekiro_read_area(process: &mut Process) -> Result<[u8; 4], GameError> {
+    let pointers = POINTERS.as_ref().unwrap();
+    let area_pointer = optional_pointer(&pointers.area, Capability::Area)?;
+
+    let mut area_buffer: [u8; 4] = [0; 4];
+    if !area_pointer.read_memory_rel(None, &mut area_buffer) {
+        return Err(GameError::ReadFailed);
+    }
+
+    // Stored as 0xAABBCCDD, -1 while not in any map
+    let area = u32::from_ne_bytes(area_buffer);
+    if area == u32::MAX {
+        return Err(GameError::ReadFailed);
+    }
+
+    return Ok(area.to_be_bytes());
 }
@@ -1,80 +1,162 @@
-use mem_rs::prelude::*;
-use std::{thread, time::Duration};
-
-use crate::utils::mem::*;
-
-pub struct GameFuncs {
-    pub script_start: unsafe fn(&mut Process), // Before script
-    pub script_end: unsafe fn(&mut Process),   // After script
-    pub frame_next: unsafe fn(&mut Process),   // Run the next frame
-    pub frame_start: unsafe fn(&mut Process),  // Start of frame, before actions
-    pub frame_end: unsafe fn(&mut Process),    // End of frame, after actions
-    pub action_fps: unsafe fn(&mut Process, f32), // Action to set FPS
-    pub flag_frame: unsafe fn(&mut Process) -> bool, // Flag to determine if a frame is running
-    pub flag_ingame: unsafe fn(&mut Process) -> bool, // Flag to determine if you are ingame and have control
-    pub flag_cutscene: unsafe fn(&mut Process) -> bool, // Flag to determine if a skippable cutscene of any kind is playing
-    pub flag_mainmenu: unsafe fn(&mut Process) -> bool, // Flag to determine if you are in the main menu
-    pub flag_position: unsafe fn(&mut Process, f32, f32, f32, f32) -> bool, // Flag to determine if you are near a position within range (X, Y, Z, Range)
-    pub flag_position_alternative: unsafe fn(&mut Process, f32, f32, f32, f32) -> bool, // Flag to determine if you are near a position within range (X, Y, Z, Range), alternative coords (different per-game, if implemented)
+use std::fmt::{self, Display, Formatter};
+
+use crate::utils::geometry::*;
+use crate::utils::input_sink::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Fps,
+    Gamepad,
+    Cutscene3d,
+    CutsceneMovie,
+    CutsceneBriefing,
+    Loading,
+    Position,
+    PositionAlternative,
+    Heading,
+    EventFlags,
+    Animation,
+    Area,
+    Hp,
+    Menu,
+    Dialog,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameError {
+    Unsupported(Capability), // The game doesn't have this at all
+    ReadFailed,              // The value couldn't be read right now, like during loading screens
+    ProcessLost,             // The game closed or can't be accessed anymore
+    Aborted,                 // The TAS was stopped with Ctrl+C
 }
 
-#[cfg(target_arch = "x86_64")]
-pub unsafe fn inject_soulmods(process: &mut Process) -> Option<ProcessModule> {
-    // Refresh process
-    process.refresh().expect("Failed to refresh process");
+impl Display for GameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::Unsupported(capability) => {
+                write!(f, "{:?} is not supported for this game", capability)
+            }
+            GameError::ReadFailed => write!(f, "Couldn't read value from game memory"),
+            GameError::ProcessLost => write!(f, "Lost access to the game process"),
+            GameError::Aborted => write!(f, "TAS aborted"),
+        }
+    }
+}
+
+pub trait Game {
+    fn name(&self) -> &'static str; // Display name of the game
+    fn capabilities(&self) -> &[Capability]; // Everything the game supports besides the basics
+
+    fn has_capability(&self, capability: Capability) -> bool {
+        return self.capabilities().contains(&capability);
+    }
 
-    // Get/Inject soulmods
-    let soulmods_module = get_or_inject_module(process, "soulmods_x64.dll");
+    fn input_sink(&self) -> Box<dyn InputSink>; // Where the inputs of the script get sent to
 
-    // Get exports
-    let soulmods_exports: Vec<ModuleExport> = get_exports(soulmods_module.clone().unwrap());
+    fn refresh(&mut self) -> Result<(), GameError>; // Make sure the game is still up
+    fn script_start(&mut self); // Before script
+    fn script_end(&mut self); // After script
+    fn frame_next(&mut self); // Run the next frame
+    fn frame_start(&mut self); // Start of frame, before actions
+    fn frame_end(&mut self); // End of frame, after actions
+    fn flag_frame(&mut self) -> bool; // Flag to determine if a frame is running
+    fn flag_focus(&mut self) -> Result<bool, GameError>; // Flag to determine if the game window is focused
+    fn flag_ingame(&mut self) -> Result<bool, GameError>; // Flag to determine if you are ingame and have control
+    fn flag_cutscene(&mut self) -> Result<bool, GameError>; // Flag to determine if a skippable cutscene of any kind is playing
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError>; // Flag to determine if you are in the main menu
 
-    // Get value to check if DLLs are initialized..
-    let ptr_soulmods_initialized = process.create_pointer(
-        soulmods_exports
-            .iter()
-            .find(|f| f.name == "SOULMODS_INITIALIZED")
-            .expect("Couldn't find SOULMODS_INITIALIZED")
-            .addr,
-        vec![0],
-    );
+    // Action to set FPS
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        return Err(GameError::Unsupported(Capability::Fps));
+    }
 
-    // ..and wait until they are
-    while !ptr_soulmods_initialized.read_bool_rel(None) {
-        thread::sleep(Duration::from_micros(10));
+    // Flag to determine if a loading screen is up
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return Err(GameError::Unsupported(Capability::Loading));
     }
 
-    return soulmods_module;
-}
+    // Current character position
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return Err(GameError::Unsupported(Capability::Position));
+    }
 
-pub unsafe fn inject_soulstas_patches(process: &mut Process) -> Option<ProcessModule> {
-    // Refresh process
-    process.refresh().expect("Failed to refresh process");
+    // Current character position, alternative coords (different per-game, same as the normal ones if there are none)
+    fn read_position_alternative(&mut self) -> Result<[f32; 3], GameError> {
+        return self.read_position();
+    }
 
-    // Get/Inject soulstas patches
-    #[cfg(target_arch = "x86_64")]
-    let soulstas_patches_module = get_or_inject_module(process, "soulstas_patches_x64.dll");
-    #[cfg(target_arch = "x86")]
-    let soulstas_patches_module = get_or_inject_module(process, "soulstas_patches_x86.dll");
+    // Current character facing angle in degrees (0-360)
+    fn read_heading(&mut self) -> Result<f32, GameError> {
+        return Err(GameError::Unsupported(Capability::Heading));
+    }
 
-    // Get exports
-    let soulstas_patches_exports: Vec<ModuleExport> =
-        get_exports(soulstas_patches_module.clone().unwrap());
+    // Flag to determine if an event flag is set
+    fn flag_event(&mut self, id: u32) -> Result<bool, GameError> {
+        return Err(GameError::Unsupported(Capability::EventFlags));
+    }
 
-    // Get value to check if DLLs are initialized..
-    let ptr_soulstas_patches_initialized = process.create_pointer(
-        soulstas_patches_exports
-            .iter()
-            .find(|f| f.name == "SOULSTAS_PATCHES_INITIALIZED")
-            .expect("Couldn't find SOULSTAS_PATCHES_INITIALIZED")
-            .addr,
-        vec![0],
-    );
+    // Current character animation ID
+    fn read_animation(&mut self) -> Result<i32, GameError> {
+        return Err(GameError::Unsupported(Capability::Animation));
+    }
 
-    // ..and wait until they are
-    while !ptr_soulstas_patches_initialized.read_bool_rel(None) {
-        thread::sleep(Duration::from_micros(10));
+    // Current map area ID (mAA_BB_CC_DD)
+    fn read_area(&mut self) -> Result<[u8; 4], GameError> {
+        return Err(GameError::Unsupported(Capability::Area));
     }
 
-    return soulstas_patches_module;
+    // Flag to determine if the character's HP is 0, which comes a moment before the death screen
+    fn flag_zero_hp(&mut self) -> Result<bool, GameError> {
+        return Err(GameError::Unsupported(Capability::Hp));
+    }
+
+    // Flag to determine if any menu is open, like the inventory or a bonfire/grace menu
+    fn flag_menu(&mut self) -> Result<bool, GameError> {
+        return Err(GameError::Unsupported(Capability::Menu));
+    }
+
+    // Flag to determine if a dialog or popup is open, like item pickups or yes/no prompts
+    fn flag_dialog(&mut self) -> Result<bool, GameError> {
+        return Err(GameError::Unsupported(Capability::Dialog));
+    }
+
+    // Flag to determine if you are near a position within range (X, Y, Z, Range)
+    fn flag_position(&mut self, x: f32, y: f32, z: f32, range: f32) -> Result<bool, GameError> {
+        return Ok(position_in_range(self.read_position()?, x, y, z, range));
+    }
+
+    // Flag to determine if you are near a position within range (X, Y, Z, Range), alternative coords
+    fn flag_position_alternative(
+        &mut self,
+        x: f32,
+        y: f32,
+        z: f32,
+        range: f32,
+    ) -> Result<bool, GameError> {
+        return Ok(position_in_range(
+            self.read_position_alternative()?,
+            x,
+            y,
+            z,
+            range,
+        ));
+    }
+
+    // Flag to determine if an animation is playing
+    fn flag_animation(&mut self, id: i32) -> Result<bool, GameError> {
+        return Ok(self.read_animation()? == id);
+    }
+
+    // Flag to determine if you are in a map area, None parts are wildcards (mAA_BB_CC_DD)
+    fn flag_area(&mut self, area: [Option<u8>; 4]) -> Result<bool, GameError> {
+        return Ok(area_matches(self.read_area()?, area));
+    }
+}
+
+// Checks if a map area ID matches a pattern, None parts match anything
+pub fn area_matches(area: [u8; 4], pattern: [Option<u8>; 4]) -> bool {
+    return area
+        .iter()
+        .zip(pattern.iter())
+        .all(|(a, p)| p.is_none() || *p == Some(*a));
 }
@@ -0,0 +1,268 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::games::shared::*;
+
+use crate::utils::input::*;
+use crate::utils::input_sink::*;
+
+// Simulated game, for running scripts and the TAS tool itself without an actual game
+
+const CAPABILITIES: &[Capability] = &[
+    Capability::Fps,
+    Capability::Gamepad,
+    Capability::Cutscene3d,
+    Capability::Loading,
+    Capability::Position,
+    Capability::PositionAlternative,
+    Capability::Heading,
+    Capability::EventFlags,
+    Capability::Animation,
+    Capability::Area,
+    Capability::Hp,
+    Capability::Menu,
+    Capability::Dialog,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimState {
+    Mainmenu,
+    Loading,
+    Ingame,
+    Cutscene,
+    Menu,   // Still ingame, but without character control
+    Dialog, // Same as a menu, for popups and prompts
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimInput {
+    pub frame: u32,
+    pub gamepad: GamepadState,
+}
+
+pub struct SimGame {
+    pub frame: u32,
+    pub fps: f32,
+    pub focused: bool,
+    pub position: [f32; 3],
+    pub heading: f32,
+    pub speed: f32, // Units per frame with the left stick fully pushed
+    pub input_log: Vec<SimInput>,
+    gamepad: Rc<RefCell<GamepadState>>, // Shared with the input sink, the runner sends to
+    states: Vec<(u32, SimState)>,
+    focus: Vec<(u32, bool)>,
+    event_flags: Vec<(u32, u32)>,
+    animations: Vec<(u32, i32)>,
+    areas: Vec<(u32, [u8; 4])>,
+    hp: Vec<(u32, i32)>,
+}
+
+impl SimGame {
+    pub fn new() -> SimGame {
+        return SimGame {
+            frame: 0,
+            fps: 0.0,
+            focused: true,
+            position: [0.0; 3],
+            heading: 0.0,
+            speed: 0.1,
+            input_log: Vec::new(),
+            gamepad: Rc::new(RefCell::new(GamepadState::default())),
+            states: Vec::new(),
+            focus: Vec::new(),
+            event_flags: Vec::new(),
+            animations: Vec::new(),
+            areas: Vec::new(),
+            hp: Vec::new(),
+        };
+    }
+
+    // Switch to a state from a frame onwards, the game starts ingame
+    pub fn schedule_state(&mut self, frame: u32, state: SimState) {
+        self.states.push((frame, state));
+    }
+
+    // Gain or lose window focus from a frame onwards
+    pub fn schedule_focus(&mut self, frame: u32, focused: bool) {
+        self.focus.push((frame, focused));
+    }
+
+    // Set an event flag from a frame onwards
+    pub fn schedule_event_flag(&mut self, frame: u32, id: u32) {
+        self.event_flags.push((frame, id));
+    }
+
+    // Play an animation from a frame onwards, until the next one
+    pub fn schedule_animation(&mut self, frame: u32, id: i32) {
+        self.animations.push((frame, id));
+    }
+
+    // Be in a map area from a frame onwards, until the next one
+    pub fn schedule_area(&mut self, frame: u32, area: [u8; 4]) {
+        self.areas.push((frame, area));
+    }
+
+    // Have a certain HP from a frame onwards, the character starts alive
+    pub fn schedule_hp(&mut self, frame: u32, hp: i32) {
+        self.hp.push((frame, hp));
+    }
+
+    // Gamepad as the game sees it right now
+    pub fn gamepad(&self) -> GamepadState {
+        return *self.gamepad.borrow();
+    }
+
+    pub fn state(&self) -> SimState {
+        return latest(&self.states, self.frame).unwrap_or(SimState::Ingame);
+    }
+}
+
+// Gamepad of the simulated game, keyboard and mouse inputs go nowhere
+struct SimGamepadSink {
+    gamepad: Rc<RefCell<GamepadState>>,
+}
+
+impl InputSink for SimGamepadSink {
+    fn send(&mut self, input: InputEvent) {
+        match input {
+            InputEvent::GamepadButton { input_type, button } => {
+                set_gamepad_button(&mut self.gamepad.borrow_mut(), button, input_type);
+            }
+            InputEvent::GamepadAxis { axis, amount } => {
+                set_gamepad_axis(&mut self.gamepad.borrow_mut(), axis, amount);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Last scheduled value that started at or before the given frame
+fn latest<T: Copy>(schedule: &Vec<(u32, T)>, frame: u32) -> Option<T> {
+    return schedule
+        .iter()
+        .filter(|(start, _)| *start <= frame)
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, value)| *value);
+}
+
+impl Game for SimGame {
+    fn name(&self) -> &'static str {
+        return "Simulation";
+    }
+
+    fn capabilities(&self) -> &[Capability] {
+        return CAPABILITIES;
+    }
+
+    fn input_sink(&self) -> Box<dyn InputSink> {
+        // Never send actual inputs to the system, only to the simulated gamepad
+        return Box::new(SimGamepadSink {
+            gamepad: self.gamepad.clone(),
+        });
+    }
+
+    fn refresh(&mut self) -> Result<(), GameError> {
+        return Ok(());
+    }
+
+    fn script_start(&mut self) {}
+
+    fn script_end(&mut self) {}
+
+    fn frame_next(&mut self) {
+        self.frame += 1;
+    }
+
+    fn frame_start(&mut self) {}
+
+    fn frame_end(&mut self) {
+        let gamepad = self.gamepad();
+        self.input_log.push(SimInput {
+            frame: self.frame,
+            gamepad: gamepad,
+        });
+
+        // Only move with character control
+        if self.state() != SimState::Ingame {
+            return;
+        }
+
+        let x = gamepad.stick_left_x as f32 / 32767.0;
+        let y = gamepad.stick_left_y as f32 / 32767.0;
+        if x == 0.0 && y == 0.0 {
+            return;
+        }
+
+        // Same angles as the gamepad stick action, 0 is forward along Z
+        self.position[0] += x * self.speed;
+        self.position[2] += y * self.speed;
+        self.heading = x.atan2(y).to_degrees().rem_euclid(360.0);
+    }
+
+    fn flag_frame(&mut self) -> bool {
+        return false;
+    }
+
+    fn flag_focus(&mut self) -> Result<bool, GameError> {
+        return Ok(latest(&self.focus, self.frame).unwrap_or(self.focused));
+    }
+
+    fn flag_ingame(&mut self) -> Result<bool, GameError> {
+        return Ok(matches!(
+            self.state(),
+            SimState::Ingame | SimState::Menu | SimState::Dialog
+        ));
+    }
+
+    fn flag_cutscene(&mut self) -> Result<bool, GameError> {
+        return Ok(self.state() == SimState::Cutscene);
+    }
+
+    fn flag_mainmenu(&mut self) -> Result<bool, GameError> {
+        return Ok(self.state() == SimState::Mainmenu);
+    }
+
+    fn action_fps(&mut self, fps: f32) -> Result<(), GameError> {
+        self.fps = fps;
+        return Ok(());
+    }
+
+    fn flag_loading(&mut self) -> Result<bool, GameError> {
+        return Ok(self.state() == SimState::Loading);
+    }
+
+    fn read_position(&mut self) -> Result<[f32; 3], GameError> {
+        return Ok(self.position);
+    }
+
+    fn read_heading(&mut self) -> Result<f32, GameError> {
+        return Ok(self.heading);
+    }
+
+    fn flag_event(&mut self, id: u32) -> Result<bool, GameError> {
+        return Ok(self
+            .event_flags
+            .iter()
+            .any(|(start, flag)| *start <= self.frame && *flag == id));
+    }
+
+    fn read_animation(&mut self) -> Result<i32, GameError> {
+        return latest(&self.animations, self.frame).ok_or(GameError::ReadFailed);
+    }
+
+    fn read_area(&mut self) -> Result<[u8; 4], GameError> {
+        return latest(&self.areas, self.frame).ok_or(GameError::ReadFailed);
+    }
+
+    fn flag_zero_hp(&mut self) -> Result<bool, GameError> {
+        return Ok(latest(&self.hp, self.frame).is_some_and(|x| x <= 0));
+    }
+
+    fn flag_menu(&mut self) -> Result<bool, GameError> {
+        return Ok(self.state() == SimState::Menu);
+    }
+
+    fn flag_dialog(&mut self) -> Result<bool, GameError> {
+        return Ok(self.state() == SimState::Dialog);
+    }
+}
@@ -0,0 +1,12 @@
+#![allow(dead_code)]
+#![allow(static_mut_refs)]
+#![allow(unreachable_patterns)]
+#![allow(unsafe_op_in_unsafe_fn)]
+#![allow(unused_variables)]
+
+pub mod cli;
+pub mod config;
+pub mod error;
+pub mod games;
+pub mod runner;
+pub mod utils;
@@ -4,376 +4,799 @@
 #![allow(unsafe_op_in_unsafe_fn)]
 #![allow(unused_variables)]
 
-use std::fs::read_to_string;
-use std::io::stdin;
-use std::{cmp, env, path::Path, process, thread, time::Duration};
+// Attaching to the games needs the Windows API, only the library builds on other systems
+#[cfg(not(windows))]
+compile_error!("The TAS tool only runs on Windows, use `cargo test --lib` to test the library.");
+
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::{env, process, thread, time::Duration};
 
 use mem_rs::prelude::*;
 
-use windows::Win32::UI::WindowsAndMessaging::*;
+use soulstas::cli::*;
+use soulstas::config::*;
+use soulstas::error::*;
+use soulstas::games::*;
+use soulstas::runner::*;
+use soulstas::utils::abort::*;
+use soulstas::utils::actions::*;
+use soulstas::utils::format::*;
+use soulstas::utils::input_sink::*;
+use soulstas::utils::lint::*;
+use soulstas::utils::mem::*;
+use soulstas::utils::version::*;
 
-mod games;
-mod utils;
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-use crate::utils::actions::*;
-use crate::utils::input::*;
-use crate::utils::mem::*;
+    if let Err(err) = run(&args) {
+        match err {
+            SoulsTasError::Usage(_) => println!("{} {}", err, usage()),
+            _ => println!("Error: {}", err),
+        }
+        process::exit(err.exit_code());
+    }
+}
 
-use crate::games::*;
+fn run(args: &[String]) -> Result<(), SoulsTasError> {
+    let mut command = parse_args(args)?;
 
-#[derive(PartialEq)]
-enum GameType {
-    DarkSouls1,
-    DarkSouls1Remastered,
-    DarkSouls2,
-    DarkSouls2Sotfs,
-    DarkSouls3,
-    Sekiro,
-    EldenRing,
-    ArmoredCore6,
-    NightReign,
-}
+    // Settings from soulstas.toml fill in what wasn't passed
+    let config = Config::load()?;
+    if let Some(dll_dir) = &config.dll_dir {
+        unsafe { set_module_dir(dll_dir.clone()) };
+    }
+    apply_config(&mut command, &config);
 
-#[cfg(target_arch = "x86_64")]
-const USAGE_TEXT: &str =
-    "Usage: soulstas_x64.exe (dsr/sotfs/ds3/sekiro/er/ac6/nr) path/to/tas/script.txt";
+    // Games of the other architecture need the other TAS tool, hand everything over to it
+    if let Some(architecture) = helper_architecture(&command)? {
+        let exit_code = run_helper(architecture, args)?;
+        process::exit(exit_code);
+    }
 
-#[cfg(target_arch = "x86")]
-const USAGE_TEXT: &str = "Usage: soulstas_x86.exe (ds1/ds2) path/to/tas/script.txt";
+    return run_command(command, &config);
+}
 
-fn main() {
-    // Parse arguments
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        println!("Invalid argument count. {}", USAGE_TEXT);
-        process::exit(0);
-    }
-
-    // Pick game
-    #[cfg(target_arch = "x86_64")]
-    let selected_game = match args[1].as_str().to_lowercase().as_str() {
-        "darksouls1remastered" | "ds1r" | "dsr" => GameType::DarkSouls1Remastered,
-        "darksouls2sotfs" | "ds2s" | "sotfs" => GameType::DarkSouls2Sotfs,
-        "darksouls3" | "ds3" => GameType::DarkSouls3,
-        "sekiro" => GameType::Sekiro,
-        "eldenring" | "er" => GameType::EldenRing,
-        "armoredcore6" | "ac6" => GameType::ArmoredCore6,
-        "nightreign" | "nr" => GameType::NightReign,
-        _ => {
-            println!("Unknown game for current architecture. {}", USAGE_TEXT);
-            process::exit(0);
+fn run_command(command: Command, config: &Config) -> Result<(), SoulsTasError> {
+    return match command {
+        Command::Run {
+            game,
+            script,
+            options,
+            start_frame,
+        } => command_run(game.as_deref(), &script, &options, config, start_frame),
+        Command::Check { game, script } => command_check(game.as_deref(), &script),
+        Command::Lint { script } => command_lint(&script),
+        Command::Fmt { script, write } => command_fmt(&script, write),
+        Command::Record {
+            game,
+            script,
+            output,
+            options,
+        } => command_record(game.as_deref(), &script, &output, &options, config),
+        Command::Monitor { game, options } => command_monitor(game.as_deref(), &options, config),
+        Command::Info { game } => command_info(game.as_deref()),
+        Command::Help { topic } => {
+            match topic.as_deref() {
+                Some("actions") => print!("{}", actions_help_text()),
+                _ => print!("{}", help_text()),
+            }
+            Ok(())
         }
     };
+}
+
+fn command_run(
+    game_name: Option<&str>,
+    script_path: &Path,
+    options: &AttachOptions,
+    config: &Config,
+    start_frame: u32,
+) -> Result<(), SoulsTasError> {
+    let tas_script = read_script(script_path)?;
+    let save_staging = stage_save(game_name, &tas_script, script_path, options, config)?;
+
+    return with_staged_save(save_staging, || {
+        let mut game = open_game(game_name, tas_script.game.as_deref(), options, config)?;
+
+        let mut runner = Runner::new(tas_script, game.as_mut());
+
+        // Make sure the game supports everything the script uses
+        runner
+            .check_capabilities()
+            .map_err(SoulsTasError::Unsupported)?;
+
+        runner.set_start_frame(start_frame);
+        let log_level = get_log_level(options, config);
+        runner.on_event(move |event| print_event(event, log_level));
+
+        // Clean up the game on Ctrl+C instead of leaving it frozen
+        install_abort_handler();
+
+        // Do TAS stuff
+        runner.run()?;
+        return Ok(());
+    });
+}
+
+fn command_check(game_name: Option<&str>, script_path: &Path) -> Result<(), SoulsTasError> {
+    let tas_script = read_script(script_path)?;
 
-    #[cfg(target_arch = "x86")]
-    let selected_game = match args[1].as_str().to_lowercase().as_str() {
-        "darksouls1" | "ds1" | "ptde" => GameType::DarkSouls1,
-        "darksouls2" | "ds2" => GameType::DarkSouls2,
-        _ => {
-            println!("Unknown game for current architecture. {}", USAGE_TEXT);
-            process::exit(0);
+    // Only needs to know the game, not attach to it
+    match game_name.or(tas_script.game.as_deref()) {
+        Some(name) if !is_simulation(name) => {
+            let info = find_game(name)?;
+            check_script_game(info, tas_script.game.as_deref())?;
+            check_capabilities(&tas_script, info.capabilities)
+                .map_err(SoulsTasError::Unsupported)?;
         }
-    };
+        _ => {}
+    }
 
-    // Try to find TAS script file
-    let tas_script_path = Path::new(&args[2]);
-    if !tas_script_path.exists() {
-        println!("Can't find TAS script. {}", USAGE_TEXT);
-        process::exit(0);
-    }
-
-    // Set up frame info vars for actions
-    let mut frame_max: u32 = 0;
-    let mut frame_previous: u32 = 0;
-    let mut frame_previous_absolute: u32 = 0;
-
-    // Create action vector
-    let mut tas_actions: Vec<TasAction> = Vec::new();
-
-    // Read TAS script and parse actions
-    for (line_num, line) in read_to_string(tas_script_path).unwrap().lines().enumerate() {
-        // Parse the action
-        let action_info: TasActionInfo = match parse_action(line) {
-            Ok(res_opt) => {
-                if let Some(res) = res_opt {
-                    res
-                } else {
-                    continue;
-                }
-            }
-            Err(err) => {
-                println!("Error in TAS script at line {}: {}", line_num + 1, err);
-                process::exit(0);
-            }
-        };
+    println!(
+        "{} actions, {} frames, no errors found.",
+        tas_script.actions.len(),
+        tas_script.frame_max + 1
+    );
+    return Ok(());
+}
 
-        // Calculate the frame
-        let frame: u32 = match action_info.frame_type {
-            FrameType::Absolute => {
-                frame_previous_absolute = action_info.frame;
-                action_info.frame
-            }
-            FrameType::Relative => action_info.frame + frame_previous,
-            FrameType::RelativeAbsolute => action_info.frame + frame_previous_absolute,
-        };
-        frame_previous = frame;
-        frame_max = cmp::max(frame_max, frame);
+fn command_lint(script_path: &Path) -> Result<(), SoulsTasError> {
+    let tas_script = read_script(script_path)?;
 
-        // Add the action to the action vector
-        tas_actions.push(TasAction {
-            frame: frame,
-            action: action_info.action,
-        });
+    let warnings = lint_script(&tas_script);
+    for warning in warnings.iter() {
+        println!("Line {}: {}", warning.line, warning.message);
+    }
+
+    if !warnings.is_empty() {
+        return Err(SoulsTasError::Script(format!(
+            "{} warning(s) found",
+            warnings.len()
+        )));
     }
 
-    // Make sure there are actually any actions
-    if tas_actions.len() <= 0 {
-        println!("No actions found in TAS script");
-        process::exit(0);
+    println!("No warnings found.");
+    return Ok(());
+}
+
+fn command_fmt(script_path: &Path, write_file: bool) -> Result<(), SoulsTasError> {
+    let tas_script_text = read_script_text(script_path)?;
+    let formatted = format_script(&tas_script_text).map_err(SoulsTasError::Script)?;
+
+    if write_file {
+        write(script_path, formatted)
+            .map_err(|err| SoulsTasError::Io(format!("Can't write TAS script: {}", err)))?;
+    } else {
+        print!("{}", formatted);
+    }
+
+    return Ok(());
+}
+
+fn command_record(
+    game_name: Option<&str>,
+    script_path: &Path,
+    output_path: &Path,
+    options: &AttachOptions,
+    config: &Config,
+) -> Result<(), SoulsTasError> {
+    let tas_script = read_script(script_path)?;
+    let save_staging = stage_save(game_name, &tas_script, script_path, options, config)?;
+    let mut recording = RecordingSink::new();
+
+    with_staged_save(save_staging, || {
+        let mut game = open_game(game_name, tas_script.game.as_deref(), options, config)?;
+
+        // Send inputs like normal, but also keep track of them
+        let game_input_sink = game.input_sink();
+        let mut runner = Runner::new(tas_script, game.as_mut());
+        runner
+            .check_capabilities()
+            .map_err(SoulsTasError::Unsupported)?;
+        runner.set_input_sink(Box::new(vec![
+            game_input_sink,
+            Box::new(&mut recording) as Box<dyn InputSink>,
+        ]));
+        let log_level = get_log_level(options, config);
+        runner.on_event(move |event| print_event(event, log_level));
+
+        install_abort_handler();
+        runner.run()?;
+        return Ok(());
+    })?;
+
+    // One line per input, with the TAS frame it was sent on
+    let mut output = String::new();
+    for recorded_frame in recording.frames.iter() {
+        for input in recorded_frame.inputs.iter() {
+            output.push_str(&format!("{} {:?}\n", recorded_frame.frame, input));
+        }
     }
+    write(output_path, output)
+        .map_err(|err| SoulsTasError::Io(format!("Can't write recording: {}", err)))?;
 
-    // Attach to game
-    let mut process: Process = match selected_game {
-        GameType::DarkSouls1 => Process::new("DARKSOULS.exe"), // TODO: Handle DATA.exe
-        GameType::DarkSouls1Remastered => {
-            println!(
-                "WARNING: DSR support might be spotty. Gamepad input is only supported if you have one plugged in."
-            );
-            Process::new("DarkSoulsRemastered.exe")
+    println!("Recording written to {}", output_path.display());
+    return Ok(());
+}
+
+fn command_monitor(
+    game_name: Option<&str>,
+    options: &AttachOptions,
+    config: &Config,
+) -> Result<(), SoulsTasError> {
+    let mut game = open_game(game_name, None, options, config)?;
+
+    println!("Monitoring {}, press Ctrl+C to stop.", game.name());
+    install_abort_handler();
+
+    while !abort_requested() {
+        game.refresh()?;
+
+        let mut parts: Vec<String> = Vec::new();
+        parts.push(format!("ingame: {}", flag_to_string(game.flag_ingame())));
+        parts.push(format!(
+            "cutscene: {}",
+            flag_to_string(game.flag_cutscene())
+        ));
+        parts.push(format!(
+            "mainmenu: {}",
+            flag_to_string(game.flag_mainmenu())
+        ));
+        if game.has_capability(Capability::Loading) {
+            parts.push(format!("loading: {}", flag_to_string(game.flag_loading())));
+        }
+        if game.has_capability(Capability::Menu) {
+            parts.push(format!("menu: {}", flag_to_string(game.flag_menu())));
         }
-        GameType::DarkSouls2 | GameType::DarkSouls2Sotfs => {
-            println!(
-                "WARNING: DS2 support isn't great and not 100% consistent. Not ready for proper TASing."
-            );
-            Process::new("DarkSoulsII.exe")
+        if game.has_capability(Capability::Dialog) {
+            parts.push(format!("dialog: {}", flag_to_string(game.flag_dialog())));
         }
-        GameType::DarkSouls3 => Process::new("DarkSoulsIII.exe"),
-        GameType::Sekiro => Process::new("sekiro.exe"),
-        GameType::EldenRing => Process::new("eldenring.exe"),
-        GameType::ArmoredCore6 => {
-            println!(
-                "WARNING: AC6 support might be spotty. Gamepad input is not supported currently and cutscene actions are not 100% reliable."
-            );
-            Process::new("armoredcore6.exe")
+        if let Ok(position) = game.read_position() {
+            parts.push(format!(
+                "position: {:.3} {:.3} {:.3}",
+                position[0], position[1], position[2]
+            ));
         }
-        GameType::NightReign => {
-            println!(
-                "WARNING: Nightreign support might be spotty due to active game updates. Gamepad input is not supported currently."
-            );
-            Process::new("nightreign.exe")
+        if let Ok(heading) = game.read_heading() {
+            parts.push(format!("heading: {:.1}", heading));
         }
-        _ => {
-            println!("Game not implemented. {}", USAGE_TEXT);
-            process::exit(0);
+        if let Ok(area) = game.read_area() {
+            parts.push(format!(
+                "area: m{:02}_{:02}_{:02}_{:02}",
+                area[0], area[1], area[2], area[3]
+            ));
+        }
+        if let Ok(animation) = game.read_animation() {
+            parts.push(format!("animation: {}", animation));
+        }
+        println!("{}", parts.join(", "));
+
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    return Ok(());
+}
+
+fn command_info(game_name: Option<&str>) -> Result<(), SoulsTasError> {
+    let game_name = match game_name {
+        Some(x) => x,
+        None => {
+            for game in GAMES {
+                println!(
+                    "{} ({}), {}, {}",
+                    game.name,
+                    game.aliases.join("/"),
+                    game.process_name,
+                    game.architecture.executable()
+                );
+            }
+            return Ok(());
         }
     };
-    process.refresh().expect("Failed to attach to process");
-
-    // Get all funcs for given game
-    #[cfg(target_arch = "x86_64")]
-    let game_funcs: GameFuncs = match selected_game {
-        GameType::DarkSouls1Remastered => unsafe { ds1r_init(&mut process) },
-        GameType::DarkSouls2Sotfs => unsafe { ds2sotfs_init(&mut process) },
-        GameType::DarkSouls3 => unsafe { ds3_init(&mut process) },
-        GameType::Sekiro => unsafe { sekiro_init(&mut process) },
-        GameType::EldenRing => unsafe { eldenring_init(&mut process) },
-        GameType::ArmoredCore6 => unsafe { armoredcore6_init(&mut process) },
-        GameType::NightReign => unsafe { nightreign_init(&mut process) },
-        _ => {
-            println!("Game not implemented. {}", USAGE_TEXT);
-            process::exit(0);
+
+    let info = find_game(game_name)?;
+    println!("Game: {}", info.name);
+    println!("Names: {}", info.aliases.join(", "));
+    println!("Process: {}", info.process_name);
+    println!("TAS tool: {}", info.architecture.executable());
+    println!(
+        "Supports: {}",
+        info.capabilities
+            .iter()
+            .map(|x| format!("{:?}", x))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    if let Some(warning) = info.warning {
+        println!("Note: {}", warning);
+    }
+
+    // Check the signatures against the running game, without injecting or patching anything
+    let running_game = match find_running_games()
+        .into_iter()
+        .find(|x| x.info.game_type == info.game_type)
+    {
+        Some(x) => x,
+        None => {
+            println!("Start {} to check its signatures.", info.name);
+            return Ok(());
         }
     };
+    if info.architecture != Architecture::current() {
+        return Err(wrong_architecture(info.game_type));
+    }
+
+    let mut process = Process::new_with_id(info.process_name, running_game.process_id);
+    process.refresh().map_err(|err| {
+        SoulsTasError::Attach(format!(
+            "Failed to attach to {}: {}",
+            info.process_name, err
+        ))
+    })?;
+
+    let module = process_module_name(&process);
+    let version = Version::from_file_version_info(PathBuf::from(process.get_path()));
+    println!("\nExecutable: {} {}", module, version);
+
+    // The hooks overwrite the functions the patches scan for, so those can't be found again
+    let patched = process
+        .get_modules()
+        .iter()
+        .any(|x| x.name.to_lowercase().starts_with("soulstas_patches"));
+    if patched {
+        println!(
+            "soulstas-patches is already loaded, restart the game to check its signatures too."
+        );
+    }
+
+    println!(
+        "\n{:<24} {:<8} {:<18} {}",
+        "Signature", "Source", "Address", "Status"
+    );
+    let checks = check_signatures(&process, info.game_type);
+    let mut failed: Vec<&str> = Vec::new();
+    for check in checks.iter() {
+        let source = match check.signature.source {
+            SignatureSource::Tool => "tool",
+            SignatureSource::Patches => "patches",
+        };
+        let (address, status) = match (check.address, check.resolved) {
+            (Some(_), Some(x)) => (format!("0x{:x}", x), "OK"),
+            // Pointers are often null until a save is loaded
+            (Some(x), None) => (format!("0x{:x}", x), "OK (null pointer)"),
+            (None, _) if patched && check.signature.source == SignatureSource::Patches => {
+                (String::from("-"), "SKIPPED")
+            }
+            (None, _) => {
+                failed.push(&check.signature.name);
+                (String::from("-"), "FAIL")
+            }
+        };
+        println!(
+            "{:<24} {:<8} {:<18} {}",
+            check.signature.name, source, address, status
+        );
+    }
+
+    println!(
+        "\n{} of {} signatures found.",
+        checks.len() - failed.len(),
+        checks.len()
+    );
+    if let Some(name) = failed.first() {
+        return Err(SoulsTasError::Signature {
+            name: name.to_string(),
+            module: module,
+            version: version.to_string(),
+        });
+    }
+
+    return Ok(());
+}
+
+fn find_game(name: &str) -> Result<&'static GameInfo, SoulsTasError> {
+    return string_to_game(name).ok_or(SoulsTasError::Usage(format!("Unknown game {}.", name)));
+}
+
+// Attaches to a game and sets it up, or creates the simulated one
+fn open_game(
+    name: Option<&str>,
+    script_game: Option<&str>,
+    options: &AttachOptions,
+    config: &Config,
+) -> Result<Box<dyn Game>, SoulsTasError> {
+    if name.is_some_and(is_simulation) {
+        return Ok(create_simulation());
+    }
+
+    let (info, process_id) = select_game(name, script_game, options)?;
+    check_script_game(info, script_game)?;
+    let options = &options.with_game_config(config.game(info.game_type));
+
+    if info.architecture != Architecture::current() {
+        return Err(wrong_architecture(info.game_type));
+    }
+
+    if let Some(warning) = info.warning {
+        println!("WARNING: {}", warning);
+    }
 
-    #[cfg(target_arch = "x86")]
-    let game_funcs: GameFuncs = match selected_game {
-        GameType::DarkSouls1 => unsafe { ds1_init(&mut process) },
-        GameType::DarkSouls2 => unsafe { ds2_init(&mut process) },
-        _ => {
-            println!("Game not implemented. {}", USAGE_TEXT);
-            process::exit(0);
+    // Attach to game, start it first if asked to
+    let process = match options.launch.as_deref() {
+        Some(target) => launch_and_wait(info, target, &options.launch_args)?,
+        None => {
+            // Several instances of a game share the process name, so attach to the chosen one
+            let mut process = match process_id {
+                Some(x) => Process::new_with_id(info.process_name, x),
+                None => Process::new(info.process_name),
+            };
+            process.refresh().map_err(|err| {
+                SoulsTasError::Attach(format!(
+                    "Failed to attach to {}, is the game running? ({})",
+                    info.process_name, err
+                ))
+            })?;
+            process
         }
     };
 
-    // Get game version and HWND
-    let process_hwnd = unsafe { get_hwnd_by_id(process.get_id()) };
+    // Set up the game
+    let game = unsafe { create_game(info.game_type, process)? };
+
+    // Optional signatures that weren't found take their actions with them
+    let missing: Vec<String> = info
+        .capabilities
+        .iter()
+        .filter(|x| !game.has_capability(**x))
+        .map(|x| format!("{:?}", x))
+        .collect();
+    if !missing.is_empty() {
+        println!(
+            "WARNING: Some signatures weren't found, scripts can't use these: {}",
+            missing.join(", ")
+        );
+    }
 
-    unsafe {
-        // Run stuff before the script starts
-        (game_funcs.script_start)(&mut process);
+    return Ok(game);
+}
+
+// Copies the save from --save or the script header into place, before the game gets launched
+fn stage_save(
+    game_name: Option<&str>,
+    tas_script: &TasScript,
+    script_path: &Path,
+    options: &AttachOptions,
+    config: &Config,
+) -> Result<Option<(&'static GameInfo, SaveStaging)>, SoulsTasError> {
+    let (save_path, slot) = match (&options.save, &tas_script.save) {
+        (Some(path), _) => (path.clone(), options.save_slot.clone()),
+        (None, Some(header)) => (
+            script_path
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(&header.path),
+            options.save_slot.clone().or(header.slot.clone()),
+        ),
+        (None, None) => return Ok(None),
+    };
+
+    if game_name.is_some_and(is_simulation) {
+        return Ok(None);
     }
 
-    // Do TAS stuff
-    let mut current_frame = 0;
-    while current_frame <= frame_max {
-        // Refresh every frame, to ensure the game is still up
-        process.refresh().expect("Failed to refresh process");
+    let (info, _) = select_game(game_name, tas_script.game.as_deref(), options)?;
+    let game_config = config.game(info.game_type);
+    let slot = slot.or(game_config.and_then(|x| x.save_slot.clone()));
+    let options = &options.with_game_config(game_config);
 
-        println!("{}", current_frame);
+    // The game only reads the save on startup
+    if options.launch.is_none() {
+        return Err(SoulsTasError::Usage(String::from(
+            "A save can only be used together with --launch.",
+        )));
+    }
 
-        unsafe {
-            // Wait for the game to finish its frame
-            while (game_funcs.flag_frame)(&mut process) {
-                thread::sleep(Duration::from_micros(10));
-            }
+    if find_running_games()
+        .iter()
+        .any(|x| x.info.game_type == info.game_type)
+    {
+        return Err(SoulsTasError::Save(format!(
+            "Close {} first, the save can't be replaced while it's running.",
+            info.name
+        )));
+    }
+
+    let save_staging = SaveStaging::stage(
+        info,
+        &save_path,
+        slot.as_deref(),
+        options.save_dir.as_deref(),
+    )?;
+    return Ok(Some((info, save_staging)));
+}
+
+// Runs the TAS between staging and restoring the save, so errors and Ctrl+C still restore it
+fn with_staged_save(
+    save_staging: Option<(&'static GameInfo, SaveStaging)>,
+    run: impl FnOnce() -> Result<(), SoulsTasError>,
+) -> Result<(), SoulsTasError> {
+    let result = run();
+    let finish_result = finish_save_staging(save_staging);
+
+    // The TAS error is the one that matters, but the save one can't get lost
+    if let (Err(_), Err(err)) = (&result, &finish_result) {
+        println!("Error: {}", err);
+    }
+    return result.and(finish_result);
+}
+
+// The game keeps writing to the save, so only put the original back once it's closed
+fn finish_save_staging(
+    save_staging: Option<(&'static GameInfo, SaveStaging)>,
+) -> Result<(), SoulsTasError> {
+    let (info, mut save_staging) = match save_staging {
+        Some(x) => x,
+        None => return Ok(()),
+    };
+
+    let is_running = || {
+        find_running_games()
+            .iter()
+            .any(|x| x.info.game_type == info.game_type)
+    };
 
-            // Do stuff at the very beginning of a frame, before the actions
-            (game_funcs.frame_start)(&mut process);
+    if is_running() {
+        // A Ctrl+C that stopped the TAS shouldn't also stop this
+        install_abort_handler();
+        clear_abort();
+
+        println!(
+            "Close {} to restore your save, or press Ctrl+C to restore it yourself later.",
+            info.name
+        );
+        while is_running() && !abort_requested() {
+            thread::sleep(Duration::from_millis(500));
         }
+    }
 
-        let running_frame = current_frame;
-        for tas_action in tas_actions.iter().filter(|x| x.frame == running_frame) {
-            match *&tas_action.action {
-                TasActionType::Key { input_type, key } => unsafe {
-                    send_key_raw(key, input_type);
-                },
-                TasActionType::KeyAlternative { input_type, key } => unsafe {
-                    send_key(key, input_type);
-                },
-                TasActionType::MouseButton { input_type, button } => unsafe {
-                    send_mouse_button(button, input_type);
-                },
-                TasActionType::MouseScroll { input_type, amount } => unsafe {
-                    send_mouse_scroll(amount, input_type);
-                },
-                TasActionType::MouseMove { x, y } => unsafe {
-                    send_mouse_move(x, y);
-                },
-                TasActionType::GamepadButton { input_type, button } => unsafe {
-                    send_gamepad_button(button, input_type);
-                },
-                TasActionType::GamepadStick {
-                    stick,
-                    angle,
-                    amount,
-                } => {
-                    let mut x = angle.to_radians().sin() * amount;
-                    x = if x >= 0.0 { x * 32767.0 } else { x * 32768.0 };
-
-                    let mut y = angle.to_radians().cos() * amount;
-                    y = if y >= 0.0 { y * 32767.0 } else { y * 32768.0 };
-
-                    match stick {
-                        GamepadStick::StickLeft => unsafe {
-                            send_gamepad_axis(GamepadAxis::StickLeftX, x.round() as i32);
-                            send_gamepad_axis(GamepadAxis::StickLeftY, y.round() as i32);
-                        },
-                        GamepadStick::StickRight => unsafe {
-                            send_gamepad_axis(GamepadAxis::StickRightX, x.round() as i32);
-                            send_gamepad_axis(GamepadAxis::StickRightY, y.round() as i32);
-                        },
-                        _ => {}
-                    }
-                }
-                TasActionType::GamepadAxis { axis, amount } => unsafe {
-                    send_gamepad_axis(axis, amount);
-                },
-                TasActionType::Nothing => { /* Does nothing on purpose */ }
-                TasActionType::Fps { fps } => unsafe {
-                    (game_funcs.action_fps)(&mut process, fps);
-                },
-                TasActionType::Await { flag } => loop {
-                    match flag {
-                        AwaitFlag::Ingame => unsafe {
-                            if (game_funcs.flag_ingame)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::NoIngame => unsafe {
-                            if !(game_funcs.flag_ingame)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::Cutscene => unsafe {
-                            if (game_funcs.flag_cutscene)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::NoCutscene => unsafe {
-                            if !(game_funcs.flag_cutscene)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::Mainmenu => unsafe {
-                            if (game_funcs.flag_mainmenu)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::NoMainmenu => unsafe {
-                            if !(game_funcs.flag_mainmenu)(&mut process) {
-                                break;
-                            }
-                        },
-                        AwaitFlag::Focus => unsafe {
-                            if GetForegroundWindow() == process_hwnd {
-                                break;
-                            }
-                        },
-                        _ => {}
-                    };
-
-                    unsafe {
-                        (game_funcs.frame_next)(&mut process);
-
-                        while (game_funcs.flag_frame)(&mut process) {
-                            thread::sleep(Duration::from_micros(10));
-                        }
-                    }
-                },
-                TasActionType::AwaitPosition { x, y, z, range } => loop {
-                    unsafe {
-                        if (game_funcs.flag_position)(&mut process, x, y, z, range) {
-                            break;
-                        }
-
-                        (game_funcs.frame_next)(&mut process);
-
-                        while (game_funcs.flag_frame)(&mut process) {
-                            thread::sleep(Duration::from_micros(10));
-                        }
-                    }
-                },
-                TasActionType::AwaitPositionAlternative { x, y, z, range } => loop {
-                    unsafe {
-                        if (game_funcs.flag_position_alternative)(&mut process, x, y, z, range) {
-                            break;
-                        }
-
-                        (game_funcs.frame_next)(&mut process);
-
-                        while (game_funcs.flag_frame)(&mut process) {
-                            thread::sleep(Duration::from_micros(10));
-                        }
-                    }
-                },
-                TasActionType::Frame { frame } => {
-                    current_frame = cmp::max(frame - 1, 0);
-                }
-                TasActionType::PauseMs { ms } => {
-                    thread::sleep(Duration::from_millis(ms));
-                }
-                TasActionType::PauseInput => {
-                    println!("Pausing. Press enter to continue.");
-                    let mut buffer = String::new();
-                    let _ = stdin().read_line(&mut buffer);
-                }
-                _ => {}
-            }
+    if is_running() {
+        return Err(save_staging.keep());
+    }
+    return save_staging.restore();
+}
+
+fn launch_and_wait(
+    info: &GameInfo,
+    target: &str,
+    args: &[String],
+) -> Result<Process, SoulsTasError> {
+    if find_running_games()
+        .iter()
+        .any(|x| x.info.game_type == info.game_type)
+    {
+        println!("{} is already running, attaching to it.", info.name);
+    } else {
+        println!("Launching {}..", info.name);
+        launch_game(target, args)?;
+    }
+
+    // Ctrl+C should stop the waiting too
+    install_abort_handler();
+
+    println!("Waiting for {} to be ready..", info.name);
+    return wait_for_game(info);
+}
+
+// Architecture of the game a command attaches to, if it isn't the one of this TAS tool
+fn helper_architecture(command: &Command) -> Result<Option<Architecture>, SoulsTasError> {
+    // Game info works anywhere, only checking the signatures needs to attach
+    if let Command::Info { game: Some(name) } = command {
+        let info = find_game(name)?;
+        let is_running = find_running_games()
+            .iter()
+            .any(|x| x.info.game_type == info.game_type);
+        if is_running && info.architecture != Architecture::current() {
+            return Ok(Some(info.architecture));
         }
+        return Ok(None);
+    }
+
+    let (name, script_path, options) = match command {
+        Command::Run {
+            game,
+            script,
+            options,
+            ..
+        }
+        | Command::Record {
+            game,
+            script,
+            options,
+            ..
+        } => (game.as_deref(), Some(script), options),
+        Command::Monitor { game, options } => (game.as_deref(), None, options),
+        _ => return Ok(None),
+    };
 
-        unsafe {
-            // Do stuff after the actions, like sending inputs
-            (game_funcs.frame_end)(&mut process);
+    if name.is_some_and(is_simulation) {
+        return Ok(None);
+    }
+
+    // A game that still has to be launched can only be known from the script
+    let script_game = match script_path {
+        Some(x) if name.is_none() => read_script(x)?.game,
+        _ => None,
+    };
+
+    let (info, _) = select_game(name, script_game.as_deref(), options)?;
+    if info.architecture == Architecture::current() {
+        return Ok(None);
+    }
+
+    return Ok(Some(info.architecture));
+}
+
+// Runs the TAS tool for the other architecture with the same arguments, returns its exit code
+fn run_helper(architecture: Architecture, args: &[String]) -> Result<i32, SoulsTasError> {
+    let helper_path = env::current_exe()
+        .ok()
+        .and_then(|x| x.parent().map(|x| x.join(architecture.executable())))
+        .filter(|x| x.exists())
+        .ok_or(SoulsTasError::Usage(format!(
+            "{} is needed for this game, put it next to {}.",
+            architecture.executable(),
+            Architecture::current().executable()
+        )))?;
+
+    // Both share the console, so Ctrl+C reaches the helper too. Keep waiting while it cleans up.
+    install_abort_handler();
+
+    let status = process::Command::new(helper_path)
+        .args(args)
+        .status()
+        .map_err(|err| {
+            SoulsTasError::Usage(format!(
+                "Failed to start {}: {}",
+                architecture.executable(),
+                err
+            ))
+        })?;
+
+    return Ok(status.code().unwrap_or(1));
+}
 
-            // Run the next frame
-            (game_funcs.frame_next)(&mut process);
+// Uses the game from the command line, otherwise the one that is running.
+// When launching, the script header can say which game to start.
+// Also returns the process ID of the running instance, if there is one.
+fn select_game(
+    name: Option<&str>,
+    script_game: Option<&str>,
+    options: &AttachOptions,
+) -> Result<(&'static GameInfo, Option<u32>), SoulsTasError> {
+    let info = match name {
+        Some(x) => Some(find_game(x)?),
+        None => None,
+    };
+
+    // Nothing is running yet when launching, so the game has to be known already
+    if options.launch.is_some() {
+        return match name.or(script_game) {
+            Some(x) => Ok((find_game(x)?, None)),
+            None => Err(SoulsTasError::Usage(String::from(
+                "Pass the game to launch, or add a `; game: (name)` comment to the script.",
+            ))),
+        };
+    }
+
+    // Running instances that fit the game and process ID
+    let running_games: Vec<RunningGame> = find_running_games()
+        .into_iter()
+        .filter(|x| info.is_none_or(|info| x.info.game_type == info.game_type))
+        .filter(|x| options.pid.is_none_or(|pid| x.process_id == pid))
+        .collect();
+
+    let running_list = running_games
+        .iter()
+        .map(|x| format!("{} with process ID {}", x.info.name, x.process_id))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    return match (running_games.len(), options.pid) {
+        // Attaching reports it properly if the game isn't running. soulstas.toml can still
+        // launch it, so the script header is good enough here.
+        (0, None) if info.is_some() => Ok((info.unwrap(), None)),
+        (0, None) if script_game.is_some() => Ok((find_game(script_game.unwrap())?, None)),
+        (0, None) => Err(SoulsTasError::Attach(String::from(
+            "No supported game is running. Start the game or pass its name.",
+        ))),
+        (0, Some(pid)) => Err(SoulsTasError::Attach(format!(
+            "No {} is running with process ID {}.",
+            info.map_or("supported game", |x| x.name),
+            pid
+        ))),
+        (1, _) => Ok((running_games[0].info, Some(running_games[0].process_id))),
+        _ if running_games
+            .iter()
+            .all(|x| x.info.game_type == running_games[0].info.game_type) =>
+        {
+            Err(SoulsTasError::Usage(format!(
+                "Several instances of {} are running ({}), pick one with --pid.",
+                running_games[0].info.name, running_list
+            )))
         }
+        _ => Err(SoulsTasError::Usage(format!(
+            "Several games are running ({}), pick one with --pid.",
+            running_list
+        ))),
+    };
+}
+
+// Makes sure the script is made for the game, if it says which one that is
+fn check_script_game(info: &GameInfo, script_game: Option<&str>) -> Result<(), SoulsTasError> {
+    let script_game = match script_game {
+        Some(x) if !is_simulation(x) => x,
+        _ => return Ok(()),
+    };
+
+    let script_info = string_to_game(script_game).ok_or(SoulsTasError::Script(format!(
+        "Unknown game {} in TAS script header.",
+        script_game
+    )))?;
 
-        current_frame += 1;
+    if script_info.game_type != info.game_type {
+        return Err(SoulsTasError::Usage(format!(
+            "TAS script is made for {}, but {} was selected.",
+            script_info.name, info.name
+        )));
     }
 
-    unsafe {
-        // Run stuff after the script, cleanup etc.
-        (game_funcs.script_end)(&mut process);
+    return Ok(());
+}
+
+fn read_script_text(path: &Path) -> Result<String, SoulsTasError> {
+    if !path.exists() {
+        return Err(SoulsTasError::Script(format!(
+            "Can't find TAS script at {}",
+            path.display()
+        )));
     }
+
+    return read_to_string(path)
+        .map_err(|err| SoulsTasError::Script(format!("Can't read TAS script: {}", err)));
+}
+
+fn read_script(path: &Path) -> Result<TasScript, SoulsTasError> {
+    return parse_script(&read_script_text(path)?).map_err(SoulsTasError::Script);
+}
+
+// --log-level wins over soulstas.toml
+fn get_log_level(options: &AttachOptions, config: &Config) -> LogLevel {
+    return options
+        .log_level
+        .or(config.log_level)
+        .unwrap_or(LogLevel::Frame);
+}
+
+// Log what's happening, as much as the log level asks for
+fn print_event(event: &RunnerEvent, log_level: LogLevel) {
+    match event {
+        RunnerEvent::FrameStarted { frame } if log_level >= LogLevel::Frame => {
+            println!("{}", frame)
+        }
+        RunnerEvent::Trace { message, .. } if log_level >= LogLevel::Info => {
+            println!("{}", message)
+        }
+        // The script waits for enter here, so this always has to show
+        RunnerEvent::Paused { .. } => println!("Pausing. Press enter to continue."),
+        _ => {}
+    }
+}
+
+fn flag_to_string(flag: Result<bool, GameError>) -> String {
+    return match flag {
+        Ok(x) => x.to_string(),
+        Err(_) => String::from("-"),
+    };
 }
@@ -0,0 +1,1026 @@
+use std::io::stdin;
+use std::{thread, time::Duration};
+
+use crate::games::shared::*;
+use crate::utils::abort::*;
+use crate::utils::actions::*;
+use crate::utils::geometry::*;
+use crate::utils::input_sink::*;
+use crate::utils::input_state::*;
+
+// Runs a parsed TAS script on a game, one TAS frame at a time
+
+#[derive(Debug, Clone)]
+pub enum RunnerEvent {
+    FrameStarted { frame: u32 },
+    ActionExecuted { frame: u32, action: TasAction },
+    AwaitSatisfied { frame: u32, action: TasAction },
+    Trace { frame: u32, message: String },
+    Paused { frame: u32 },
+}
+
+pub struct Runner<'a> {
+    script: TasScript,
+    game: &'a mut dyn Game,
+    input_sink: Box<dyn InputSink + 'a>,
+    input_state: InputState,        // What the script wants to be held
+    input_sent: InputState,         // What was last sent to the game
+    input_pending: Vec<InputEvent>, // Inputs that aren't held, like mouse movement
+    input_resync: bool,             // Focus was lost, so the game might have missed inputs
+    callback: Option<Box<dyn FnMut(&RunnerEvent) + 'a>>,
+    current_frame: u32,
+    started: bool,
+    finished: bool,
+
+    // Values to log every frame
+    trace_animation: bool,
+    trace_area: bool,
+    trace_speed: bool,
+    trace_heading: bool,
+    trace_inputs: bool,
+    trace_position_history: PositionHistory,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(script: TasScript, game: &'a mut dyn Game) -> Runner<'a> {
+        let input_sink = game.input_sink();
+        return Runner {
+            script: script,
+            game: game,
+            input_sink: input_sink,
+            input_state: InputState::new(),
+            input_sent: InputState::new(),
+            input_pending: Vec::new(),
+            input_resync: false,
+            callback: None,
+            current_frame: 0,
+            started: false,
+            finished: false,
+            trace_animation: false,
+            trace_area: false,
+            trace_speed: false,
+            trace_heading: false,
+            trace_inputs: false,
+            trace_position_history: PositionHistory::default(),
+        };
+    }
+
+    // Gets called for everything that happens during the run
+    pub fn on_event(&mut self, callback: impl FnMut(&RunnerEvent) + 'a) {
+        self.callback = Some(Box::new(callback));
+    }
+
+    // Replaces the input sink the game picked, like with a RecordingSink
+    pub fn set_input_sink(&mut self, input_sink: Box<dyn InputSink + 'a>) {
+        self.input_sink = input_sink;
+    }
+
+    pub fn current_frame(&self) -> u32 {
+        return self.current_frame;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return self.finished;
+    }
+
+    // Inputs the script currently wants to be held
+    pub fn input_state(&self) -> &InputState {
+        return &self.input_state;
+    }
+
+    // Makes sure the game supports everything the script uses
+    pub fn check_capabilities(&self) -> Result<(), String> {
+        return check_capabilities(&self.script, self.game.capabilities());
+    }
+
+    // Starts the script in the middle, inputs held by earlier actions are held from the start
+    pub fn set_start_frame(&mut self, frame: u32) {
+        self.current_frame = frame;
+
+        for tas_action in self.script.actions.iter() {
+            if tas_action.frame >= frame {
+                continue;
+            }
+
+            for input in tas_action.action.input_events() {
+                self.input_state.apply(input);
+            }
+
+            if let TasActionType::Trace { value, enabled } = tas_action.action {
+                match value {
+                    TraceValue::Animation => self.trace_animation = enabled,
+                    TraceValue::Area => self.trace_area = enabled,
+                    TraceValue::Speed => self.trace_speed = enabled,
+                    TraceValue::Heading => self.trace_heading = enabled,
+                    TraceValue::Inputs => self.trace_inputs = enabled,
+                }
+            }
+        }
+    }
+
+    // Runs the whole script
+    pub fn run(&mut self) -> Result<(), GameError> {
+        while self.step()? {}
+        return Ok(());
+    }
+
+    // Runs until the given TAS frame is reached or the script is done
+    pub fn run_until(&mut self, frame: u32) -> Result<(), GameError> {
+        while self.current_frame < frame && self.step()? {}
+        return Ok(());
+    }
+
+    // Runs a single TAS frame, returns false once the script is done
+    pub fn step(&mut self) -> Result<bool, GameError> {
+        let result = self.step_frame();
+        if result.is_err() {
+            self.stop();
+        }
+        return result;
+    }
+
+    // Stops the script early, the game is left the same way as after a normal end
+    pub fn stop(&mut self) {
+        if self.started && !self.finished {
+            self.release_inputs();
+            self.game.script_end();
+            self.finished = true;
+        }
+    }
+
+    fn step_frame(&mut self) -> Result<bool, GameError> {
+        if self.finished {
+            return Ok(false);
+        }
+
+        if !self.started {
+            // Run stuff before the script starts
+            self.game.script_start();
+            self.started = true;
+        }
+
+        // Refresh every frame, to ensure the game is still up
+        self.game.refresh()?;
+
+        self.emit(RunnerEvent::FrameStarted {
+            frame: self.current_frame,
+        });
+
+        // Wait for the game to finish its frame
+        self.wait_frame()?;
+
+        // Do stuff at the very beginning of a frame, before the actions
+        self.game.frame_start();
+
+        // Log traced values
+        self.trace();
+
+        let running_frame = self.current_frame;
+        for i in 0..self.script.actions.len() {
+            let tas_action = self.script.actions[i];
+            if tas_action.frame != running_frame {
+                continue;
+            }
+
+            // Inputs only change the wanted state, it gets sent at the end of the frame
+            for input in tas_action.action.input_events() {
+                if !self.input_state.apply(input) {
+                    self.input_pending.push(input);
+                }
+            }
+
+            // Inputs before an await on the same frame should reach the game while waiting
+            if tas_action.action.is_await() {
+                self.send_inputs(running_frame);
+            }
+
+            match tas_action.action {
+                TasActionType::Nothing => { /* Does nothing on purpose */ }
+                TasActionType::Fps { fps } => {
+                    self.game.action_fps(fps)?;
+                }
+                TasActionType::Await { flag } => loop {
+                    match flag {
+                        AwaitFlag::Ingame => {
+                            if await_result(self.game.flag_ingame())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::NoIngame => {
+                            if await_result(self.game.flag_ingame().map(|x| !x))? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Cutscene => {
+                            if await_result(self.game.flag_cutscene())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::NoCutscene => {
+                            if await_result(self.game.flag_cutscene().map(|x| !x))? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Mainmenu => {
+                            if await_result(self.game.flag_mainmenu())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::NoMainmenu => {
+                            if await_result(self.game.flag_mainmenu().map(|x| !x))? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Loading => {
+                            if await_result(self.game.flag_loading())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::NoLoading => {
+                            if await_result(self.game.flag_loading().map(|x| !x))? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::ZeroHp => {
+                            if await_result(self.game.flag_zero_hp())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Menu => {
+                            if await_result(self.game.flag_menu())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::NoMenu => {
+                            if await_result(self.game.flag_menu().map(|x| !x))? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Dialog => {
+                            if await_result(self.game.flag_dialog())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Focus => {
+                            if await_result(self.game.flag_focus())? {
+                                break;
+                            }
+                        }
+                        AwaitFlag::Area { area } => {
+                            if await_result(self.game.flag_area(area))? {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    };
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitPosition { x, y, z, range } => loop {
+                    if await_result(self.game.flag_position(x, y, z, range))? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitPositionAlternative { x, y, z, range } => loop {
+                    if await_result(self.game.flag_position_alternative(x, y, z, range))? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitRegion { region } => loop {
+                    if await_result(self.game.read_position().map(|x| region.contains(x)))? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitStill { threshold } => {
+                    let mut position_history = PositionHistory::default();
+                    loop {
+                        position_history.push(await_value(self.game.read_position())?);
+                        if let Some(speed) = position_history.speed {
+                            if speed <= threshold {
+                                break;
+                            }
+                        }
+
+                        self.next_frame()?;
+                    }
+                }
+                TasActionType::AwaitSpeed {
+                    comparison,
+                    speed: target,
+                } => {
+                    let mut position_history = PositionHistory::default();
+                    loop {
+                        position_history.push(await_value(self.game.read_position())?);
+                        if let Some(speed) = position_history.speed {
+                            if comparison.compare(speed, target) {
+                                break;
+                            }
+                        }
+
+                        self.next_frame()?;
+                    }
+                }
+                TasActionType::AwaitFacing { heading, tolerance } => loop {
+                    if await_result(
+                        self.game
+                            .read_heading()
+                            .map(|x| heading_within(x, heading, tolerance)),
+                    )? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitEventFlag { id, state } => loop {
+                    if await_result(self.game.flag_event(id).map(|x| x == state))? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::AwaitAnimation { id, playing } => loop {
+                    if await_result(self.game.flag_animation(id).map(|x| x == playing))? {
+                        break;
+                    }
+
+                    self.next_frame()?;
+                },
+                TasActionType::Frame { frame } => {
+                    self.current_frame = frame.saturating_sub(1);
+                }
+                TasActionType::PauseMs { ms } => {
+                    thread::sleep(Duration::from_millis(ms));
+                }
+                TasActionType::PauseInput => {
+                    self.emit(RunnerEvent::Paused {
+                        frame: running_frame,
+                    });
+                    let mut buffer = String::new();
+                    let _ = stdin().read_line(&mut buffer);
+                }
+                TasActionType::Trace { value, enabled } => match value {
+                    TraceValue::Animation => {
+                        self.trace_animation = enabled;
+                    }
+                    TraceValue::Area => {
+                        self.trace_area = enabled;
+                    }
+                    TraceValue::Speed => {
+                        self.trace_speed = enabled;
+                        self.trace_position_history = PositionHistory::default();
+                    }
+                    TraceValue::Heading => {
+                        self.trace_heading = enabled;
+                    }
+                    TraceValue::Inputs => {
+                        self.trace_inputs = enabled;
+                    }
+                },
+                _ => {}
+            }
+
+            if tas_action.action.is_await() {
+                self.emit(RunnerEvent::AwaitSatisfied {
+                    frame: running_frame,
+                    action: tas_action,
+                });
+            }
+            self.emit(RunnerEvent::ActionExecuted {
+                frame: running_frame,
+                action: tas_action,
+            });
+        }
+
+        // Send out the inputs of this frame
+        self.send_inputs(running_frame);
+
+        // Do stuff after the actions
+        self.game.frame_end();
+
+        // Run the next frame
+        self.game.frame_next();
+
+        self.current_frame += 1;
+
+        if self.current_frame > self.script.frame_max {
+            // Run stuff after the script, cleanup etc.
+            self.stop();
+        }
+
+        return Ok(!self.finished);
+    }
+
+    fn trace(&mut self) {
+        let frame = self.current_frame;
+
+        if self.trace_animation {
+            let message = if let Ok(animation) = self.game.read_animation() {
+                format!("Animation: {}", animation)
+            } else {
+                String::from("Animation: -")
+            };
+            self.emit(RunnerEvent::Trace { frame, message });
+        }
+        if self.trace_area {
+            let message = if let Ok(area) = self.game.read_area() {
+                format!(
+                    "Area: m{:02}_{:02}_{:02}_{:02}",
+                    area[0], area[1], area[2], area[3]
+                )
+            } else {
+                String::from("Area: -")
+            };
+            self.emit(RunnerEvent::Trace { frame, message });
+        }
+        if self.trace_speed {
+            self.trace_position_history
+                .push(self.game.read_position().ok());
+            let message = if let Some(speed) = self.trace_position_history.speed {
+                format!("Speed: {}", speed)
+            } else {
+                String::from("Speed: -")
+            };
+            self.emit(RunnerEvent::Trace { frame, message });
+        }
+        if self.trace_heading {
+            let message = if let Ok(heading) = self.game.read_heading() {
+                format!("Heading: {}", heading)
+            } else {
+                String::from("Heading: -")
+            };
+            self.emit(RunnerEvent::Trace { frame, message });
+        }
+        if self.trace_inputs {
+            let message = format!("Inputs: {}", self.input_state);
+            self.emit(RunnerEvent::Trace { frame, message });
+        }
+    }
+
+    // Waits for the game to finish its frame, unless the TAS gets aborted
+    fn wait_frame(&mut self) -> Result<(), GameError> {
+        while self.game.flag_frame() {
+            if abort_requested() {
+                return Err(GameError::Aborted);
+            }
+            thread::sleep(Duration::from_micros(10));
+        }
+
+        if abort_requested() {
+            return Err(GameError::Aborted);
+        }
+
+        return Ok(());
+    }
+
+    fn next_frame(&mut self) -> Result<(), GameError> {
+        self.game.frame_next();
+        return self.wait_frame();
+    }
+
+    // Lets go of everything, no matter if the game has focus
+    fn release_inputs(&mut self) {
+        self.input_state = InputState::new();
+        self.input_pending.clear();
+
+        // Releases first, then every axis back to rest
+        let inputs = self.input_state.diff(&self.input_sent);
+        for input in inputs.into_iter().chain(self.input_state.full()) {
+            self.input_sink.send(input);
+        }
+        self.input_sink.flush(self.current_frame);
+        self.input_sent = InputState::new();
+    }
+
+    // Sends what changed since the last time, or everything once the game has focus again
+    fn send_inputs(&mut self, frame: u32) {
+        // Keyboard and mouse only reach the focused window, so hold them off until it's back.
+        // The gamepad gets written into the game, that works without focus.
+        let focused = self.game.flag_focus().unwrap_or(true);
+        let input_state = if focused {
+            self.input_state.clone()
+        } else {
+            self.input_sent.with_gamepad_of(&self.input_state)
+        };
+
+        let inputs = if self.input_resync && focused {
+            input_state.full()
+        } else {
+            input_state.diff(&self.input_sent)
+        };
+
+        // Mouse movement and scrolling are never for the gamepad
+        let input_pending: Vec<InputEvent> = if focused {
+            self.input_pending.drain(..).collect()
+        } else {
+            Vec::new()
+        };
+
+        for input in inputs.into_iter().chain(input_pending) {
+            self.input_sink.send(input);
+        }
+        self.input_sink.flush(frame);
+
+        self.input_sent = input_state;
+        self.input_resync = !focused;
+    }
+
+    fn emit(&mut self, event: RunnerEvent) {
+        if let Some(callback) = self.callback.as_mut() {
+            callback(&event);
+        }
+    }
+}
+
+// Makes sure a game with the given capabilities supports everything a script uses
+pub fn check_capabilities(script: &TasScript, capabilities: &[Capability]) -> Result<(), String> {
+    for tas_action in script.actions.iter() {
+        let required = tas_action.action.required_capabilities();
+        if !required.is_empty() && !required.iter().any(|x| capabilities.contains(x)) {
+            return Err(format!(
+                "Error in TAS script at line {}: {}",
+                tas_action.line,
+                GameError::Unsupported(required[0])
+            ));
+        }
+    }
+
+    return Ok(());
+}
+
+// Makes sure the game isn't left frozen with inputs held, even on errors or panics
+impl<'a> Drop for Runner<'a> {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Turns a game query into a flag for awaits, values that can't be read right now just keep waiting
+fn await_result(result: Result<bool, GameError>) -> Result<bool, GameError> {
+    match result {
+        Ok(x) => return Ok(x),
+        Err(GameError::ReadFailed) => return Ok(false),
+        Err(err) => return Err(err),
+    }
+}
+
+// Same for values, None while they can't be read right now
+fn await_value<T>(result: Result<T, GameError>) -> Result<Option<T>, GameError> {
+    match result {
+        Ok(x) => return Ok(Some(x)),
+        Err(GameError::ReadFailed) => return Ok(None),
+        Err(err) => return Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::sim::*;
+    use crate::utils::input::*;
+
+    const BUTTON_A: u16 = 1 << 12;
+
+    fn run_script(game: &mut SimGame, script: &str) -> Vec<RunnerEvent> {
+        let mut events: Vec<RunnerEvent> = Vec::new();
+        {
+            let mut runner = Runner::new(parse_script(script).unwrap(), game);
+            runner.on_event(|event| events.push(event.clone()));
+            runner.run().unwrap();
+        }
+        return events;
+    }
+
+    fn logged_frames(game: &SimGame) -> Vec<u32> {
+        return game.input_log.iter().map(|x| x.frame).collect();
+    }
+
+    #[test]
+    fn stick_moves_on_the_frames_it_is_held() {
+        let mut game = SimGame::new();
+        run_script(
+            &mut game,
+            "0 gamepad stick left 0 1\n\
+             10 gamepad stick left 0 0",
+        );
+
+        assert_eq!(logged_frames(&game), (0..=10).collect::<Vec<u32>>());
+        for input in game.input_log.iter() {
+            let expected = if input.frame < 10 { 32767 } else { 0 };
+            assert_eq!(
+                input.gamepad.stick_left_y, expected,
+                "frame {}",
+                input.frame
+            );
+            assert_eq!(input.gamepad.stick_left_x, 0, "frame {}", input.frame);
+        }
+
+        // 10 frames forward along Z, facing 0 degrees
+        assert!((game.position[2] - 10.0 * game.speed).abs() < 0.0001);
+        assert_eq!(game.position[0], 0.0);
+        assert_eq!(game.heading, 0.0);
+    }
+
+    #[test]
+    fn buttons_are_held_until_released() {
+        let mut game = SimGame::new();
+        run_script(
+            &mut game,
+            "0 gamepad button down a\n\
+             3 gamepad button up a\n\
+             5 nothing",
+        );
+
+        let held: Vec<bool> = game
+            .input_log
+            .iter()
+            .map(|x| x.gamepad.buttons & BUTTON_A != 0)
+            .collect();
+        assert_eq!(held, vec![true, true, true, false, false, false]);
+    }
+
+    #[test]
+    fn no_movement_without_character_control() {
+        let mut game = SimGame::new();
+        game.schedule_state(2, SimState::Cutscene);
+        run_script(
+            &mut game,
+            "0 gamepad stick left 90 1\n\
+             4 nothing",
+        );
+
+        // Only frames 0 and 1 are ingame, going right along X
+        assert!((game.position[0] - 2.0 * game.speed).abs() < 0.0001);
+        assert_eq!(game.heading, 90.0);
+    }
+
+    #[test]
+    fn menu_takes_control_but_time_goes_on() {
+        let mut game = SimGame::new();
+        game.schedule_state(3, SimState::Menu);
+        game.schedule_state(6, SimState::Ingame);
+        run_script(
+            &mut game,
+            "0 gamepad stick left 0 1\n\
+             10 gamepad stick left 0 0",
+        );
+
+        // Every frame gets its input, but frames 3 to 5 don't move the character
+        assert_eq!(logged_frames(&game), (0..=10).collect::<Vec<u32>>());
+        assert_eq!(game.input_log[4].gamepad.stick_left_y, 32767);
+        assert!((game.position[2] - 7.0 * game.speed).abs() < 0.0001);
+    }
+
+    #[test]
+    fn await_menu_and_no_menu() {
+        let mut game = SimGame::new();
+        game.schedule_state(3, SimState::Menu);
+        game.schedule_state(7, SimState::Ingame);
+        run_script(
+            &mut game,
+            "0 gamepad button down a\n\
+             1 gamepad button up a\n\
+             1 await menu\n\
+             2 gamepad button down b\n\
+             2 await no_menu\n\
+             3 nothing",
+        );
+
+        // Waits on TAS frame 1 until the menu opens on frame 3, then on TAS frame 2 until it's gone
+        assert_eq!(logged_frames(&game), vec![0, 3, 7, 8]);
+        assert_eq!(game.input_log[2].gamepad.buttons, 1 << 13);
+    }
+
+    #[test]
+    fn await_dialog_ignores_other_menus() {
+        let mut game = SimGame::new();
+        game.schedule_state(2, SimState::Menu);
+        game.schedule_state(4, SimState::Ingame);
+        game.schedule_state(6, SimState::Dialog);
+        run_script(
+            &mut game,
+            "0 await dialog\n\
+             1 nothing",
+        );
+
+        assert_eq!(logged_frames(&game), vec![6, 7]);
+    }
+
+    #[test]
+    fn menus_dont_count_as_loading() {
+        for script in ["0 await loading\n1 nothing", "0 await no_ingame\n1 nothing"] {
+            let mut game = SimGame::new();
+            game.schedule_state(2, SimState::Menu);
+            game.schedule_state(4, SimState::Ingame);
+            game.schedule_state(6, SimState::Loading);
+            run_script(&mut game, script);
+
+            assert_eq!(logged_frames(&game), vec![6, 7], "{}", script);
+        }
+    }
+
+    #[test]
+    fn await_ingame_waits_for_the_state() {
+        let mut game = SimGame::new();
+        game.schedule_state(0, SimState::Loading);
+        game.schedule_state(5, SimState::Ingame);
+        let events = run_script(
+            &mut game,
+            "0 await ingame\n\
+             +1 gamepad button down a",
+        );
+
+        // The await holds the game on TAS frame 0 until frame 5, the next action follows right after
+        assert_eq!(logged_frames(&game), vec![5, 6]);
+        assert_eq!(game.input_log[0].gamepad.buttons, 0);
+        assert_eq!(game.input_log[1].gamepad.buttons, BUTTON_A);
+
+        let satisfied: Vec<u32> = events
+            .iter()
+            .filter_map(|x| match x {
+                RunnerEvent::AwaitSatisfied { frame, .. } => Some(*frame),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(satisfied, vec![0]);
+    }
+
+    #[test]
+    fn await_already_satisfied_doesnt_wait() {
+        let mut game = SimGame::new();
+        run_script(
+            &mut game,
+            "0 await ingame\n\
+             1 nothing",
+        );
+
+        assert_eq!(logged_frames(&game), vec![0, 1]);
+    }
+
+    #[test]
+    fn inputs_before_an_await_are_held_while_waiting() {
+        let mut game = SimGame::new();
+        game.schedule_event_flag(4, 100);
+        run_script(
+            &mut game,
+            "0 gamepad button down a\n\
+             0 await event_flag 100\n\
+             1 gamepad button up a",
+        );
+
+        assert_eq!(logged_frames(&game), vec![4, 5]);
+        assert_eq!(game.input_log[0].gamepad.buttons, BUTTON_A);
+        assert_eq!(game.input_log[1].gamepad.buttons, 0);
+    }
+
+    #[test]
+    fn await_zero_hp_waits_for_the_hp() {
+        let mut game = SimGame::new();
+        game.schedule_hp(0, 100);
+        game.schedule_hp(3, 0);
+        run_script(
+            &mut game,
+            "0 await zero_hp\n\
+             1 nothing",
+        );
+
+        assert_eq!(logged_frames(&game), vec![3, 4]);
+    }
+
+    #[test]
+    fn unreadable_values_keep_waiting() {
+        let mut game = SimGame::new();
+        game.schedule_animation(3, 1000);
+        game.schedule_area(6, [10, 0, 0, 0]);
+        run_script(
+            &mut game,
+            "0 await animation 1000\n\
+             1 await area m10_*\n\
+             2 nothing",
+        );
+
+        // No animation or area at all before they are scheduled, which reads the same as a loading screen
+        assert_eq!(logged_frames(&game), vec![3, 6, 7]);
+    }
+
+    #[test]
+    fn script_end_lets_go_of_everything() {
+        let mut game = SimGame::new();
+        run_script(
+            &mut game,
+            "0 gamepad button down a\n\
+             0 gamepad stick left 45 1\n\
+             2 nothing",
+        );
+
+        // Nothing gets logged after the script, so look at the gamepad itself
+        let gamepad = game.gamepad();
+        assert_eq!(gamepad.buttons, 0);
+        assert_eq!(gamepad.stick_left_x, 0);
+        assert_eq!(gamepad.stick_left_y, 0);
+    }
+
+    fn record_script(script: &str) -> RecordingSink {
+        let mut game = SimGame::new();
+        let mut recording = RecordingSink::new();
+        {
+            let mut runner = Runner::new(parse_script(script).unwrap(), &mut game);
+            runner.set_input_sink(Box::new(&mut recording));
+            runner.run().unwrap();
+        }
+        return recording;
+    }
+
+    fn key(input_type: InputType, name: &str) -> InputEvent {
+        return InputEvent::Key {
+            input_type: input_type,
+            key: string_to_keycode(name).unwrap(),
+        };
+    }
+
+    fn button(input_type: InputType, button: GamepadButton) -> InputEvent {
+        return InputEvent::GamepadButton {
+            input_type: input_type,
+            button: button,
+        };
+    }
+
+    // Every axis back to rest, what the end of a script sends
+    fn axes_at_rest() -> Vec<InputEvent> {
+        return InputState::new().full();
+    }
+
+    #[test]
+    fn recording_has_every_press_and_release() {
+        let recording = record_script(
+            "0 key down w\n\
+             0 gamepad button down a\n\
+             2 key down shift\n\
+             2 key up w\n\
+             4 key up shift\n\
+             4 gamepad button up a\n\
+             5 mouse move 10 0\n\
+             6 nothing",
+        );
+
+        let frames: Vec<(u32, Vec<InputEvent>)> = recording
+            .frames
+            .iter()
+            .map(|x| (x.frame, x.inputs.clone()))
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                (
+                    0,
+                    vec![
+                        key(InputType::Down, "w"),
+                        button(InputType::Down, GamepadButton::A)
+                    ]
+                ),
+                // Releases always come first
+                (
+                    2,
+                    vec![key(InputType::Up, "w"), key(InputType::Down, "shift")]
+                ),
+                (
+                    4,
+                    vec![
+                        key(InputType::Up, "shift"),
+                        button(InputType::Up, GamepadButton::A)
+                    ]
+                ),
+                (5, vec![InputEvent::MouseMove { x: 10, y: 0 }]),
+                (7, axes_at_rest()),
+            ]
+        );
+    }
+
+    #[test]
+    fn recording_only_has_changes_of_held_inputs() {
+        let recording = record_script(
+            "0 key down w\n\
+             1 key down w\n\
+             2 key up a\n\
+             3 gamepad axis l2 255\n\
+             4 gamepad axis l2 255\n\
+             5 key up w",
+        );
+
+        // Pressing a held key again or releasing one that isn't held sends nothing
+        assert_eq!(recording.inputs_at(0), vec![key(InputType::Down, "w")]);
+        assert_eq!(recording.inputs_at(1), vec![]);
+        assert_eq!(recording.inputs_at(2), vec![]);
+        assert_eq!(
+            recording.inputs_at(3),
+            vec![InputEvent::GamepadAxis {
+                axis: GamepadAxis::TriggerLeft,
+                amount: 255
+            }]
+        );
+        assert_eq!(recording.inputs_at(4), vec![]);
+        assert_eq!(recording.inputs_at(5), vec![key(InputType::Up, "w")]);
+        assert_eq!(
+            recording
+                .frames
+                .iter()
+                .map(|x| x.frame)
+                .collect::<Vec<u32>>(),
+            vec![0, 3, 5, 6]
+        );
+    }
+
+    #[test]
+    fn recording_releases_held_inputs_when_stopped_early() {
+        let mut game = SimGame::new();
+        let mut recording = RecordingSink::new();
+        {
+            let script = "0 key down w\n\
+                          0 gamepad stick left 90 1\n\
+                          10 key up w";
+            let mut runner = Runner::new(parse_script(script).unwrap(), &mut game);
+            runner.set_input_sink(Box::new(&mut recording));
+            runner.run_until(3).unwrap();
+            runner.stop();
+        }
+
+        // The released stick comes first, then every axis is set to rest again
+        let mut expected = vec![
+            key(InputType::Up, "w"),
+            InputEvent::GamepadAxis {
+                axis: GamepadAxis::StickLeftX,
+                amount: 0,
+            },
+        ];
+        expected.extend(axes_at_rest());
+        assert_eq!(recording.inputs_at(3), expected);
+        assert_eq!(
+            recording
+                .frames
+                .iter()
+                .map(|x| x.frame)
+                .collect::<Vec<u32>>(),
+            vec![0, 3]
+        );
+    }
+
+    #[test]
+    fn recording_matches_the_input_timeline() {
+        let script = "0 key down w\n\
+                      0 key_alternative down a\n\
+                      3 mouse button down left\n\
+                      3 key up w\n\
+                      6 mouse button up left\n\
+                      6 key_alternative up a";
+        let recording = record_script(script);
+
+        // Replaying the recorded inputs gives the same held state as the timeline on every frame
+        let mut input_state = InputState::new();
+        for (frame, expected) in input_timeline(&parse_script(script).unwrap()) {
+            for input in recording.inputs_at(frame) {
+                input_state.apply(input);
+            }
+            assert_eq!(input_state, expected, "frame {}", frame);
+        }
+    }
+
+    #[test]
+    fn gamepad_doesnt_wait_for_focus() {
+        let mut game = SimGame::new();
+        game.schedule_focus(1, false);
+        game.schedule_focus(4, true);
+        let mut recording = RecordingSink::new();
+        {
+            let script = "1 key down w\n\
+                          1 gamepad button down a\n\
+                          2 mouse move 5 5\n\
+                          3 key up w\n\
+                          3 key down e\n\
+                          5 nothing";
+            let mut runner = Runner::new(parse_script(script).unwrap(), &mut game);
+            runner.set_input_sink(Box::new(&mut recording));
+            runner.run_until(5).unwrap();
+        }
+
+        // Keyboard and mouse are held back until focus is back, then everything gets sent again
+        assert_eq!(
+            recording.inputs_at(1),
+            vec![button(InputType::Down, GamepadButton::A)]
+        );
+        assert_eq!(recording.inputs_at(2), vec![]);
+        assert_eq!(recording.inputs_at(3), vec![]);
+
+        let mut expected = vec![
+            key(InputType::Down, "e"),
+            button(InputType::Down, GamepadButton::A),
+        ];
+        expected.extend(axes_at_rest());
+        expected.push(InputEvent::MouseMove { x: 5, y: 5 });
+        assert_eq!(recording.inputs_at(4), expected);
+    }
+}
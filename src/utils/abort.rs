@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(windows)]
+use windows::Win32::System::Console::*;
+#[cfg(windows)]
+use windows_core::BOOL;
+
+// Ctrl+C shouldn't kill the TAS tool right away, the game has to be cleaned up first
+
+static ABORT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(windows)]
+unsafe extern "system" fn console_ctrl_handler(ctrl_type: u32) -> BOOL {
+    if ctrl_type == CTRL_C_EVENT || ctrl_type == CTRL_BREAK_EVENT {
+        request_abort();
+        return BOOL(1);
+    }
+
+    return BOOL(0);
+}
+
+// Catch Ctrl+C and Ctrl+Break in the console, so the runner can stop on its own
+#[cfg(windows)]
+pub fn install_abort_handler() -> bool {
+    return unsafe { SetConsoleCtrlHandler(Some(console_ctrl_handler), true).is_ok() };
+}
+
+#[cfg(not(windows))]
+pub fn install_abort_handler() -> bool {
+    return false;
+}
+
+pub fn request_abort() {
+    ABORT_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+// For waiting on something else after the runner was stopped
+pub fn clear_abort() {
+    ABORT_REQUESTED.store(false, Ordering::SeqCst);
+}
+
+pub fn abort_requested() -> bool {
+    return ABORT_REQUESTED.load(Ordering::SeqCst);
+}
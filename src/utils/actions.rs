@@ -1,6 +1,10 @@
-use windows::Win32::UI::Input::KeyboardAndMouse::*;
+use std::cmp;
+use std::path::PathBuf;
 
+use crate::games::shared::Capability;
+use crate::utils::geometry::*;
 use crate::utils::input::*;
+use crate::utils::input_sink::*;
 
 #[derive(Debug, Clone, Copy)]
 pub enum AwaitFlag {
@@ -10,7 +14,23 @@ pub enum AwaitFlag {
     NoCutscene,
     Mainmenu,
     NoMainmenu,
+    Loading,
+    NoLoading,
+    ZeroHp,
+    Menu,
+    NoMenu,
+    Dialog,
     Focus,
+    Area { area: [Option<u8>; 4] },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TraceValue {
+    Animation,
+    Area,
+    Speed,
+    Heading,
+    Inputs,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -67,6 +87,28 @@ pub enum TasActionType {
         z: f32,
         range: f32,
     },
+    AwaitRegion {
+        region: Region,
+    },
+    AwaitStill {
+        threshold: f32,
+    },
+    AwaitSpeed {
+        comparison: Comparison,
+        speed: f32,
+    },
+    AwaitFacing {
+        heading: f32,
+        tolerance: f32,
+    },
+    AwaitEventFlag {
+        id: u32,
+        state: bool,
+    },
+    AwaitAnimation {
+        id: i32,
+        playing: bool,
+    },
     Frame {
         frame: u32,
     },
@@ -74,14 +116,339 @@ pub enum TasActionType {
         ms: u64,
     },
     PauseInput,
+    Trace {
+        value: TraceValue,
+        enabled: bool,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct TasAction {
     pub frame: u32,
+    pub line: usize,
     pub action: TasActionType,
 }
 
+impl TasActionType {
+    // Actions that wait for something in the game, possibly over multiple frames
+    pub fn is_await(&self) -> bool {
+        return matches!(
+            self,
+            TasActionType::Await { .. }
+                | TasActionType::AwaitPosition { .. }
+                | TasActionType::AwaitPositionAlternative { .. }
+                | TasActionType::AwaitRegion { .. }
+                | TasActionType::AwaitStill { .. }
+                | TasActionType::AwaitSpeed { .. }
+                | TasActionType::AwaitFacing { .. }
+                | TasActionType::AwaitEventFlag { .. }
+                | TasActionType::AwaitAnimation { .. }
+        );
+    }
+
+    // Inputs this action sends, sticks are split into their two axes
+    pub fn input_events(&self) -> Vec<InputEvent> {
+        return match *self {
+            TasActionType::Key { input_type, key } => vec![InputEvent::Key { input_type, key }],
+            TasActionType::KeyAlternative { input_type, key } => {
+                vec![InputEvent::KeyAlternative { input_type, key }]
+            }
+            TasActionType::MouseButton { input_type, button } => {
+                vec![InputEvent::MouseButton { input_type, button }]
+            }
+            TasActionType::MouseScroll { input_type, amount } => {
+                vec![InputEvent::MouseScroll { input_type, amount }]
+            }
+            TasActionType::MouseMove { x, y } => vec![InputEvent::MouseMove { x, y }],
+            TasActionType::GamepadButton { input_type, button } => {
+                vec![InputEvent::GamepadButton { input_type, button }]
+            }
+            TasActionType::GamepadStick {
+                stick,
+                angle,
+                amount,
+            } => {
+                let mut x = angle.to_radians().sin() * amount;
+                x = if x >= 0.0 { x * 32767.0 } else { x * 32768.0 };
+
+                let mut y = angle.to_radians().cos() * amount;
+                y = if y >= 0.0 { y * 32767.0 } else { y * 32768.0 };
+
+                let (axis_x, axis_y) = match stick {
+                    GamepadStick::StickLeft => (GamepadAxis::StickLeftX, GamepadAxis::StickLeftY),
+                    GamepadStick::StickRight => {
+                        (GamepadAxis::StickRightX, GamepadAxis::StickRightY)
+                    }
+                };
+
+                vec![
+                    InputEvent::GamepadAxis {
+                        axis: axis_x,
+                        amount: x.round() as i32,
+                    },
+                    InputEvent::GamepadAxis {
+                        axis: axis_y,
+                        amount: y.round() as i32,
+                    },
+                ]
+            }
+            TasActionType::GamepadAxis { axis, amount } => {
+                vec![InputEvent::GamepadAxis { axis, amount }]
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    // Capabilities a game needs for this action, any one of them is enough
+    pub fn required_capabilities(&self) -> &'static [Capability] {
+        return match self {
+            TasActionType::GamepadButton { .. }
+            | TasActionType::GamepadAxis { .. }
+            | TasActionType::GamepadStick { .. } => &[Capability::Gamepad],
+            TasActionType::Fps { .. } => &[Capability::Fps],
+            TasActionType::Await { flag } => match flag {
+                AwaitFlag::Cutscene | AwaitFlag::NoCutscene => &[
+                    Capability::Cutscene3d,
+                    Capability::CutsceneMovie,
+                    Capability::CutsceneBriefing,
+                ],
+                AwaitFlag::Loading | AwaitFlag::NoLoading => &[Capability::Loading],
+                AwaitFlag::ZeroHp => &[Capability::Hp],
+                AwaitFlag::Menu | AwaitFlag::NoMenu => &[Capability::Menu],
+                AwaitFlag::Dialog => &[Capability::Dialog],
+                AwaitFlag::Area { .. } => &[Capability::Area],
+                _ => &[],
+            },
+            TasActionType::AwaitPosition { .. }
+            | TasActionType::AwaitPositionAlternative { .. }
+            | TasActionType::AwaitRegion { .. }
+            | TasActionType::AwaitStill { .. }
+            | TasActionType::AwaitSpeed { .. } => &[Capability::Position],
+            TasActionType::AwaitFacing { .. } => &[Capability::Heading],
+            TasActionType::AwaitEventFlag { .. } => &[Capability::EventFlags],
+            TasActionType::AwaitAnimation { .. } => &[Capability::Animation],
+            TasActionType::Trace { value, .. } => match value {
+                TraceValue::Animation => &[Capability::Animation],
+                TraceValue::Area => &[Capability::Area],
+                TraceValue::Speed => &[Capability::Position],
+                TraceValue::Heading => &[Capability::Heading],
+                TraceValue::Inputs => &[],
+            },
+            _ => &[],
+        };
+    }
+}
+
+// Syntax of every action, for help texts
+pub struct ActionInfo {
+    pub syntax: &'static str,
+    pub description: &'static str,
+    pub example: &'static str, // Has to parse, checked by the tests
+}
+
+pub const ACTIONS: &[ActionInfo] = &[
+    ActionInfo {
+        syntax: "key (down/up) (key)",
+        description: "Press or release a key",
+        example: "key down w",
+    },
+    ActionInfo {
+        syntax: "key_alternative (down/up) (key)",
+        description: "Press or release a key (alternative, for the character name box specifically)",
+        example: "key_alternative up a",
+    },
+    ActionInfo {
+        syntax: "gamepad button (down/up) (button)",
+        description: "Press or release a gamepad button",
+        example: "gamepad button down a",
+    },
+    ActionInfo {
+        syntax: "gamepad stick (left/right) (angle) (amount, 0-1)",
+        description: "Set a gamepad stick position",
+        example: "gamepad stick left 90 1",
+    },
+    ActionInfo {
+        syntax: "gamepad axis (axis) (amount)",
+        description: "Set a gamepad axis position",
+        example: "gamepad axis l2 255",
+    },
+    ActionInfo {
+        syntax: "mouse button (down/up) (button)",
+        description: "Press or release a mouse button",
+        example: "mouse button down left",
+    },
+    ActionInfo {
+        syntax: "mouse scroll (down/up) (amount)",
+        description: "Scroll the mouse wheel",
+        example: "mouse scroll up 2",
+    },
+    ActionInfo {
+        syntax: "mouse move (x) (y)",
+        description: "Move the mouse",
+        example: "mouse move 10 -5",
+    },
+    ActionInfo {
+        syntax: "await ingame",
+        description: "Wait for being loaded in with character control",
+        example: "await ingame",
+    },
+    ActionInfo {
+        syntax: "await no_ingame",
+        description: "Wait for not being loaded in with character control",
+        example: "await no_ingame",
+    },
+    ActionInfo {
+        syntax: "await cutscene",
+        description: "Wait for cutscene",
+        example: "await cutscene",
+    },
+    ActionInfo {
+        syntax: "await no_cutscene",
+        description: "Wait for no cutscene",
+        example: "await no_cutscene",
+    },
+    ActionInfo {
+        syntax: "await mainmenu",
+        description: "Wait for being in the main menu",
+        example: "await mainmenu",
+    },
+    ActionInfo {
+        syntax: "await no_mainmenu",
+        description: "Wait for not being in the main menu",
+        example: "await no_mainmenu",
+    },
+    ActionInfo {
+        syntax: "await loading",
+        description: "Wait for a loading screen",
+        example: "await loading",
+    },
+    ActionInfo {
+        syntax: "await no_loading",
+        description: "Wait for no loading screen",
+        example: "await no_loading",
+    },
+    ActionInfo {
+        syntax: "await zero_hp",
+        description: "Wait for the character's HP to drop to 0, a moment before the death screen",
+        example: "await zero_hp",
+    },
+    ActionInfo {
+        syntax: "await menu",
+        description: "Wait for any menu to be open",
+        example: "await menu",
+    },
+    ActionInfo {
+        syntax: "await no_menu",
+        description: "Wait for no menu to be open",
+        example: "await no_menu",
+    },
+    ActionInfo {
+        syntax: "await dialog",
+        description: "Wait for a dialog or popup, like item pickups or yes/no prompts",
+        example: "await dialog",
+    },
+    ActionInfo {
+        syntax: "await position (x) (y) (z) (range)",
+        description: "Wait for the character to be near a given position",
+        example: "await position 10.5 -20 3 1",
+    },
+    ActionInfo {
+        syntax: "await position_alternative (x) (y) (z) (range)",
+        description: "Wait for the character to be near a given position using alternative coordinates",
+        example: "await position_alternative 10.5 -20 3 1",
+    },
+    ActionInfo {
+        syntax: "await region box (x1) (y1) (z1) (x2) (y2) (z2)",
+        description: "Wait for the character to be inside an axis-aligned box",
+        example: "await region box 0 0 0 10 5 10",
+    },
+    ActionInfo {
+        syntax: "await region cylinder (x) (z) (radius)",
+        description: "Wait for the character to be within a horizontal radius, ignoring height",
+        example: "await region cylinder 10 20 2.5",
+    },
+    ActionInfo {
+        syntax: "await region plane (x) (y) (z) (normal x) (normal y) (normal z)",
+        description: "Wait for the character to cross a plane, as in being on the side its normal points to",
+        example: "await region plane 0 0 0 1 0 0",
+    },
+    ActionInfo {
+        syntax: "await region axis (x/y/z) (</<=/>/>=) (value)",
+        description: "Wait for a single coordinate to pass a value, for example await region axis y < -50",
+        example: "await region axis y < -50",
+    },
+    ActionInfo {
+        syntax: "await still (threshold, optional)",
+        description: "Wait for the character to fully stop moving, optionally with a max speed per frame (default 0.001)",
+        example: "await still 0.01",
+    },
+    ActionInfo {
+        syntax: "await speed (</<=/>/>=) (speed)",
+        description: "Wait for the character's speed per frame to compare to a value, for example await speed > 0.1",
+        example: "await speed > 0.1",
+    },
+    ActionInfo {
+        syntax: "await facing (degrees) (tolerance)",
+        description: "Wait for the character to face a direction in degrees, within a tolerance",
+        example: "await facing 90 5",
+    },
+    ActionInfo {
+        syntax: "await event_flag (id) (on/off, optional)",
+        description: "Wait for an event flag to be set (or unset, with off)",
+        example: "await event_flag 100 off",
+    },
+    ActionInfo {
+        syntax: "await animation (id)",
+        description: "Wait for the character to play an animation",
+        example: "await animation 1000",
+    },
+    ActionInfo {
+        syntax: "await no_animation (id)",
+        description: "Wait for the character to not play an animation",
+        example: "await no_animation 1000",
+    },
+    ActionInfo {
+        syntax: "await area (id)",
+        description: "Wait for the character to be in a map area (for example m60_42_36_00, m60_* or m10_01_*)",
+        example: "await area m10_01_*",
+    },
+    ActionInfo {
+        syntax: "nothing",
+        description: "Do nothing",
+        example: "nothing",
+    },
+    ActionInfo {
+        syntax: "fps (fps)",
+        description: "Set the FPS limit (use 0 to reset)",
+        example: "fps 30",
+    },
+    ActionInfo {
+        syntax: "await focus",
+        description: "Wait until you are tabbed in",
+        example: "await focus",
+    },
+    ActionInfo {
+        syntax: "frame (frame)",
+        description: "Set the TAS frame",
+        example: "frame 100",
+    },
+    ActionInfo {
+        syntax: "pause ms (ms)",
+        description: "Pause for an amount of milliseconds",
+        example: "pause ms 500",
+    },
+    ActionInfo {
+        syntax: "pause input",
+        description: "Pause until you press enter in the terminal window",
+        example: "pause input",
+    },
+    ActionInfo {
+        syntax: "trace (animation/area/speed/heading/inputs) (on/off)",
+        description: "Log a value every frame, for example to find animation IDs or to see which inputs are held",
+        example: "trace inputs on",
+    },
+];
+
 #[derive(Debug, Clone, Copy)]
 pub enum FrameType {
     Absolute,
@@ -89,6 +456,20 @@ pub enum FrameType {
     RelativeAbsolute,
 }
 
+pub struct TasScript {
+    pub actions: Vec<TasAction>,
+    pub frame_max: u32,
+    pub game: Option<String>, // From a `; game: (name)` comment before the first action
+    pub save: Option<SaveHeader>,
+}
+
+// Save file to copy into the game's save folder before the TAS, from `@save (path) [slot]`
+#[derive(Debug, Clone)]
+pub struct SaveHeader {
+    pub path: PathBuf, // Relative to the script
+    pub slot: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct TasActionInfo {
     pub frame: u32,
@@ -445,6 +826,201 @@ pub fn parse_action(input: &str) -> Result<Option<TasActionInfo>, &str> {
                         },
                     }
                 }
+                "event_flag" => {
+                    // Event flag, optionally with the state to wait for
+                    if params.len() != 2 && params.len() != 3 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::AwaitEventFlag {
+                        id: if let Ok(id) = params[1].parse::<u32>() {
+                            id
+                        } else {
+                            return Err("Invalid event flag");
+                        },
+                        state: if params.len() == 3 {
+                            match params[2].to_lowercase().as_str() {
+                                "on" => true,
+                                "off" => false,
+                                _ => {
+                                    return Err("Invalid event flag state");
+                                }
+                            }
+                        } else {
+                            true
+                        },
+                    }
+                }
+                "region" => {
+                    // Position inside of a shape, like a box or below a certain height
+                    if params.len() < 2 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    let region_type = params[1].to_lowercase();
+                    let region_params = &params[2..];
+
+                    TasActionType::AwaitRegion {
+                        region: match region_type.as_str() {
+                            "box" | "cylinder" | "plane" => {
+                                let param_count = if region_type == "cylinder" { 3 } else { 6 };
+                                if region_params.len() != param_count {
+                                    return Err("Invalid parameter count");
+                                }
+
+                                let mut values: Vec<f32> = Vec::new();
+                                for region_param in region_params {
+                                    if let Ok(x) = region_param.parse::<f32>() {
+                                        values.push(x);
+                                    } else {
+                                        return Err("Invalid region value");
+                                    }
+                                }
+
+                                match region_type.as_str() {
+                                    "box" => Region::Box {
+                                        min: [
+                                            values[0].min(values[3]),
+                                            values[1].min(values[4]),
+                                            values[2].min(values[5]),
+                                        ],
+                                        max: [
+                                            values[0].max(values[3]),
+                                            values[1].max(values[4]),
+                                            values[2].max(values[5]),
+                                        ],
+                                    },
+                                    "cylinder" => Region::Cylinder {
+                                        x: values[0],
+                                        z: values[1],
+                                        radius: values[2],
+                                    },
+                                    _ => Region::Plane {
+                                        point: [values[0], values[1], values[2]],
+                                        normal: [values[3], values[4], values[5]],
+                                    },
+                                }
+                            }
+                            "axis" => {
+                                if region_params.len() != 3 {
+                                    return Err("Invalid parameter count");
+                                }
+
+                                Region::Axis {
+                                    axis: if let Some(axis) =
+                                        string_to_position_axis(region_params[0])
+                                    {
+                                        axis
+                                    } else {
+                                        return Err("Invalid axis");
+                                    },
+                                    comparison: if let Some(comparison) =
+                                        string_to_comparison(region_params[1])
+                                    {
+                                        comparison
+                                    } else {
+                                        return Err("Invalid comparison");
+                                    },
+                                    value: if let Ok(x) = region_params[2].parse::<f32>() {
+                                        x
+                                    } else {
+                                        return Err("Invalid region value");
+                                    },
+                                }
+                            }
+                            _ => {
+                                return Err("Invalid region type");
+                            }
+                        },
+                    }
+                }
+                "still" => {
+                    // Movement speed per frame below a threshold
+                    if params.len() > 2 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::AwaitStill {
+                        threshold: if params.len() == 2 {
+                            if let Ok(x) = params[1].parse::<f32>() {
+                                x
+                            } else {
+                                return Err("Invalid threshold");
+                            }
+                        } else {
+                            0.001
+                        },
+                    }
+                }
+                "speed" => {
+                    // Movement speed per frame compared to a value
+                    if params.len() != 3 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::AwaitSpeed {
+                        comparison: if let Some(comparison) = string_to_comparison(params[1]) {
+                            comparison
+                        } else {
+                            return Err("Invalid comparison");
+                        },
+                        speed: if let Ok(x) = params[2].parse::<f32>() {
+                            x
+                        } else {
+                            return Err("Invalid speed");
+                        },
+                    }
+                }
+                "facing" => {
+                    // Heading in degrees, within a tolerance
+                    if params.len() != 3 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::AwaitFacing {
+                        heading: if let Ok(x) = params[1].parse::<f32>() {
+                            x
+                        } else {
+                            return Err("Invalid heading");
+                        },
+                        tolerance: if let Ok(x) = params[2].parse::<f32>() {
+                            x
+                        } else {
+                            return Err("Invalid tolerance");
+                        },
+                    }
+                }
+                "area" => {
+                    // Map area ID like m60_42_36_00, with * or missing parts as wildcards
+                    if params.len() != 2 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::Await {
+                        flag: AwaitFlag::Area {
+                            area: if let Some(area) = string_to_area(params[1]) {
+                                area
+                            } else {
+                                return Err("Invalid area");
+                            },
+                        },
+                    }
+                }
+                "animation" | "no_animation" => {
+                    // Animation ID, either playing or not
+                    if params.len() != 2 {
+                        return Err("Invalid parameter count");
+                    }
+
+                    TasActionType::AwaitAnimation {
+                        id: if let Ok(id) = params[1].parse::<i32>() {
+                            id
+                        } else {
+                            return Err("Invalid animation");
+                        },
+                        playing: params[0].to_lowercase() == "animation",
+                    }
+                }
                 _ => {
                     if params.len() != 1 {
                         return Err("Invalid parameter count");
@@ -458,6 +1034,12 @@ pub fn parse_action(input: &str) -> Result<Option<TasActionInfo>, &str> {
                             "no_cutscene" => AwaitFlag::NoCutscene,
                             "mainmenu" => AwaitFlag::Mainmenu,
                             "no_mainmenu" => AwaitFlag::NoMainmenu,
+                            "loading" => AwaitFlag::Loading,
+                            "no_loading" => AwaitFlag::NoLoading,
+                            "zero_hp" => AwaitFlag::ZeroHp,
+                            "menu" => AwaitFlag::Menu,
+                            "no_menu" => AwaitFlag::NoMenu,
+                            "dialog" => AwaitFlag::Dialog,
                             "focus" => AwaitFlag::Focus,
                             _ => {
                                 return Err("Invalid await flag");
@@ -511,6 +1093,31 @@ pub fn parse_action(input: &str) -> Result<Option<TasActionInfo>, &str> {
                 }
             }
         }
+        "trace" => {
+            if params.len() != 2 {
+                return Err("Invalid parameter count");
+            }
+
+            TasActionType::Trace {
+                value: match params[0].to_lowercase().as_str() {
+                    "animation" => TraceValue::Animation,
+                    "area" => TraceValue::Area,
+                    "speed" => TraceValue::Speed,
+                    "heading" => TraceValue::Heading,
+                    "inputs" => TraceValue::Inputs,
+                    _ => {
+                        return Err("Invalid trace value");
+                    }
+                },
+                enabled: match params[1].to_lowercase().as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => {
+                        return Err("Invalid trace state");
+                    }
+                },
+            }
+        }
         _ => {
             return Err("Invalid action");
         }
@@ -523,3 +1130,210 @@ pub fn parse_action(input: &str) -> Result<Option<TasActionInfo>, &str> {
         action: action,
     }));
 }
+
+// Parses a whole TAS script and calculates the frame of every action
+pub fn parse_script(script: &str) -> Result<TasScript, String> {
+    // Set up frame info vars for actions
+    let mut frame_max: u32 = 0;
+    let mut frame_previous: u32 = 0;
+    let mut frame_previous_absolute: u32 = 0;
+
+    // Create action vector
+    let mut tas_actions: Vec<TasAction> = Vec::new();
+    let mut game: Option<String> = None;
+    let mut save: Option<SaveHeader> = None;
+
+    for (line_num, line) in script.lines().enumerate() {
+        // Header lines like `@save path/to/ER0000.sl2`
+        if line.trim_start().starts_with('@') {
+            match parse_save_header(line) {
+                Ok(x) => save = Some(x),
+                Err(err) => {
+                    return Err(format!(
+                        "Error in TAS script at line {}: {}",
+                        line_num + 1,
+                        err
+                    ));
+                }
+            }
+            continue;
+        }
+
+        // Check the header for the game the script is made for
+        if tas_actions.is_empty() && game.is_none() {
+            game = parse_header_game(line);
+        }
+
+        // Parse the action
+        let action_info: TasActionInfo = match parse_action(line) {
+            Ok(res_opt) => {
+                if let Some(res) = res_opt {
+                    res
+                } else {
+                    continue;
+                }
+            }
+            Err(err) => {
+                return Err(format!(
+                    "Error in TAS script at line {}: {}",
+                    line_num + 1,
+                    err
+                ));
+            }
+        };
+
+        // Calculate the frame
+        let frame: u32 = match action_info.frame_type {
+            FrameType::Absolute => {
+                frame_previous_absolute = action_info.frame;
+                action_info.frame
+            }
+            FrameType::Relative => action_info.frame + frame_previous,
+            FrameType::RelativeAbsolute => action_info.frame + frame_previous_absolute,
+        };
+        frame_previous = frame;
+        frame_max = cmp::max(frame_max, frame);
+
+        // Add the action to the action vector
+        tas_actions.push(TasAction {
+            frame: frame,
+            line: line_num + 1,
+            action: action_info.action,
+        });
+    }
+
+    // Make sure there are actually any actions
+    if tas_actions.is_empty() {
+        return Err(String::from("No actions found in TAS script"));
+    }
+
+    return Ok(TasScript {
+        actions: tas_actions,
+        frame_max: frame_max,
+        game: game,
+        save: save,
+    });
+}
+
+// Parses `@save (path) [slot]`, paths with spaces need quotes
+pub fn parse_save_header(input: &str) -> Result<SaveHeader, &str> {
+    let input = input.trim();
+    let rest = match input.strip_prefix("@save") {
+        Some(x) if x.starts_with(char::is_whitespace) => x.trim_start(),
+        _ => return Err("Invalid header"),
+    };
+
+    let (path, rest) = if let Some(quoted) = rest.strip_prefix('"') {
+        match quoted.split_once('"') {
+            Some(x) => x,
+            None => return Err("Missing closing quote"),
+        }
+    } else {
+        let uncommented = rest.split(&[';', '#']).next().unwrap();
+        uncommented
+            .split_once(char::is_whitespace)
+            .unwrap_or((uncommented.trim_end(), ""))
+    };
+
+    if path.is_empty() {
+        return Err("Missing save path");
+    }
+
+    // Remove comments from the rest
+    let mut rest_parts = rest.split(&[';', '#']).next().unwrap().split_whitespace();
+    let slot = rest_parts.next().map(|x| x.to_string());
+    if rest_parts.next().is_some() {
+        return Err("Too many arguments");
+    }
+
+    return Ok(SaveHeader {
+        path: PathBuf::from(path),
+        slot: slot,
+    });
+}
+
+// Parses a header comment like `; game: eldenring`
+fn parse_header_game(line: &str) -> Option<String> {
+    let comment = line.trim_start().strip_prefix(&[';', '#'])?;
+    let (key, value) = comment.split_once(':')?;
+
+    if !key.trim().eq_ignore_ascii_case("game") || value.trim().is_empty() {
+        return None;
+    }
+
+    return Some(value.trim().to_lowercase());
+}
+
+// Parses a map area ID like m60_42_36_00 or m60_*, None parts match anything
+pub fn string_to_area(area_string: &str) -> Option<[Option<u8>; 4]> {
+    let area_string = area_string.to_lowercase();
+    let area_string = area_string.strip_prefix('m').unwrap_or(&area_string);
+
+    let parts: Vec<&str> = area_string.split('_').collect();
+    if parts.len() > 4 {
+        return None;
+    }
+
+    let mut area: [Option<u8>; 4] = [None; 4];
+    for (i, part) in parts.iter().enumerate() {
+        if *part == "*" {
+            continue;
+        }
+
+        if let Ok(x) = part.parse::<u8>() {
+            area[i] = Some(x);
+        } else {
+            return None;
+        }
+    }
+
+    return Some(area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_action_example_parses() {
+        for info in ACTIONS {
+            let line = format!("0 {}", info.example);
+            let result = parse_action(&line);
+            assert!(
+                matches!(result, Ok(Some(_))),
+                "`{}` doesn't parse: {:?}",
+                info.example,
+                result.err()
+            );
+        }
+    }
+
+    #[test]
+    fn every_action_example_matches_its_syntax() {
+        for info in ACTIONS {
+            // Everything before the first parameter has to be written the same way
+            let keywords: Vec<&str> = info
+                .syntax
+                .split_whitespace()
+                .take_while(|x| !x.starts_with('('))
+                .collect();
+            let words: Vec<&str> = info
+                .example
+                .split_whitespace()
+                .take(keywords.len())
+                .collect();
+            assert_eq!(words, keywords, "example of `{}`", info.syntax);
+        }
+    }
+
+    #[test]
+    fn every_action_is_listed_once() {
+        for (i, info) in ACTIONS.iter().enumerate() {
+            assert!(
+                !ACTIONS[..i].iter().any(|x| x.syntax == info.syntax),
+                "`{}` is listed twice",
+                info.syntax
+            );
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::utils::actions::*;
+
+// Cleans up the formatting of a script without changing what it does:
+// single spaces between arguments, lowercase actions, no trailing whitespace and no double empty lines
+pub fn format_script(script: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for (line_num, line) in script.lines().enumerate() {
+        // Header lines are kept as they are, their paths can be case sensitive
+        if line.trim_start().starts_with('@') {
+            if let Err(err) = parse_save_header(line) {
+                return Err(format!(
+                    "Error in TAS script at line {}: {}",
+                    line_num + 1,
+                    err
+                ));
+            }
+            lines.push(line.trim().to_string());
+            continue;
+        }
+
+        // Only reformat lines that are valid in the first place
+        if let Err(err) = parse_action(line) {
+            return Err(format!(
+                "Error in TAS script at line {}: {}",
+                line_num + 1,
+                err
+            ));
+        }
+
+        let (code, comment) = if let Some(x) = line.find(&[';', '#']) {
+            line.split_at(x)
+        } else {
+            (line, "")
+        };
+
+        let code = code
+            .split_whitespace()
+            .map(|x| x.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let comment = comment.trim_end();
+
+        let formatted = if code.is_empty() {
+            comment.to_string()
+        } else if comment.is_empty() {
+            code
+        } else {
+            format!("{} {}", code, comment)
+        };
+
+        if formatted.is_empty() && lines.last().is_some_and(|x| x.is_empty()) {
+            continue;
+        }
+        lines.push(formatted);
+    }
+
+    // Exactly one newline at the end
+    while lines.last().is_some_and(|x| x.is_empty()) {
+        lines.pop();
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    return Ok(output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spacing_and_case_are_cleaned_up() {
+        let formatted = format_script(
+            "0   KEY Down   w  \n\
+             \n\
+             \n\
+             10 key up w   ; Stop running  \n\
+             \t# Just a comment\n\
+             \n",
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            "0 key down w\n\n10 key up w ; Stop running\n# Just a comment\n"
+        );
+    }
+
+    #[test]
+    fn header_case_is_kept() {
+        let formatted = format_script("  @save Saves/Run.sl2\n0 nothing").unwrap();
+        assert_eq!(formatted, "@save Saves/Run.sl2\n0 nothing\n");
+    }
+
+    #[test]
+    fn formatting_twice_changes_nothing() {
+        let formatted = format_script("0 key down w\n\n\n5   key up W").unwrap();
+        assert_eq!(format_script(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn invalid_lines_are_errors() {
+        assert_eq!(
+            format_script("0 nothing\n5 jump")
+                .unwrap_err()
+                .split(':')
+                .next(),
+            Some("Error in TAS script at line 2")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// Shared checks for positions, used by the position and region awaits
+
+#[derive(Debug, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Comparison {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Region {
+    Box {
+        min: [f32; 3],
+        max: [f32; 3],
+    },
+    Cylinder {
+        x: f32,
+        z: f32,
+        radius: f32,
+    },
+    Plane {
+        point: [f32; 3],
+        normal: [f32; 3],
+    },
+    Axis {
+        axis: Axis,
+        comparison: Comparison,
+        value: f32,
+    },
+}
+
+impl Comparison {
+    pub fn compare(&self, a: f32, b: f32) -> bool {
+        return match self {
+            Comparison::Less => a < b,
+            Comparison::LessEqual => a <= b,
+            Comparison::Greater => a > b,
+            Comparison::GreaterEqual => a >= b,
+        };
+    }
+}
+
+impl Region {
+    pub fn contains(&self, position: [f32; 3]) -> bool {
+        match *self {
+            Region::Box { min, max } => {
+                return (0..3).all(|i| position[i] >= min[i] && position[i] <= max[i]);
+            }
+            Region::Cylinder { x, z, radius } => {
+                // Ignores height, a negative radius means outside of it
+                let distance = ((position[0] - x).powf(2.0) + (position[2] - z).powf(2.0)).sqrt();
+                if radius >= 0.0 {
+                    return distance <= radius;
+                } else {
+                    return distance >= radius.abs();
+                }
+            }
+            Region::Plane { point, normal } => {
+                // On the side the normal points to, or on the plane itself
+                let dot = (0..3)
+                    .map(|i| (position[i] - point[i]) * normal[i])
+                    .sum::<f32>();
+                return dot >= 0.0;
+            }
+            Region::Axis {
+                axis,
+                comparison,
+                value,
+            } => {
+                let position_value = match axis {
+                    Axis::X => position[0],
+                    Axis::Y => position[1],
+                    Axis::Z => position[2],
+                };
+                return comparison.compare(position_value, value);
+            }
+        }
+    }
+}
+
+// Checks if a position is within range of a point, a negative range checks if it's outside instead
+pub fn position_in_range(position: [f32; 3], x: f32, y: f32, z: f32, range: f32) -> bool {
+    if range == 0.0 && position[0] == x && position[1] == y && position[2] == z {
+        return true;
+    }
+
+    let distance =
+        ((position[0] - x).powf(2.0) + (position[1] - y).powf(2.0) + (position[2] - z).powf(2.0))
+            .sqrt();
+
+    if range > 0.0 && distance <= range {
+        return true;
+    }
+
+    if range < 0.0 && distance >= range.abs() {
+        return true;
+    }
+
+    return false;
+}
+
+pub fn string_to_position_axis(name: &str) -> Option<Axis> {
+    return match name.to_lowercase().as_str() {
+        "x" => Some(Axis::X),
+        "y" => Some(Axis::Y),
+        "z" => Some(Axis::Z),
+        _ => None,
+    };
+}
+
+pub fn string_to_comparison(name: &str) -> Option<Comparison> {
+    return match name {
+        "<" => Some(Comparison::Less),
+        "<=" => Some(Comparison::LessEqual),
+        ">" => Some(Comparison::Greater),
+        ">=" => Some(Comparison::GreaterEqual),
+        _ => None,
+    };
+}
+
+// Keeps the last position around to get the per-frame movement speed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PositionHistory {
+    previous: Option<[f32; 3]>,
+    pub speed: Option<f32>,
+}
+
+impl PositionHistory {
+    pub fn push(&mut self, position: Option<[f32; 3]>) {
+        self.speed = match (self.previous, position) {
+            (Some(previous), Some(current)) => Some(
+                ((current[0] - previous[0]).powf(2.0)
+                    + (current[1] - previous[1]).powf(2.0)
+                    + (current[2] - previous[2]).powf(2.0))
+                .sqrt(),
+            ),
+            _ => None,
+        };
+        self.previous = position;
+    }
+}
+
+// Checks if a heading is within tolerance of another, both in degrees
+pub fn heading_within(heading: f32, target: f32, tolerance: f32) -> bool {
+    let difference = (heading - target + 540.0).rem_euclid(360.0) - 180.0;
+    return difference.abs() <= tolerance;
+}
+
+// Converts an in-game angle in radians to a heading in degrees, from 0 to 360
+pub fn radians_to_heading(angle: f32) -> f32 {
+    return angle.to_degrees().rem_euclid(360.0);
+}
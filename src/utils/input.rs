@@ -1,21 +1,14 @@
-use std::mem::*;
-
+#[cfg(windows)]
 use windows::Win32::UI::Input::KeyboardAndMouse::*;
-use windows::Win32::UI::Input::XboxController::*;
-use windows::Win32::UI::WindowsAndMessaging::*;
 
-pub static mut XINPUT_STATE_OVERRIDE: XINPUT_STATE = XINPUT_STATE {
-    dwPacketNumber: 0,
-    Gamepad: XINPUT_GAMEPAD {
-        wButtons: XINPUT_GAMEPAD_BUTTON_FLAGS(0),
-        bLeftTrigger: 0,
-        bRightTrigger: 0,
-        sThumbLX: 0,
-        sThumbLY: 0,
-        sThumbRX: 0,
-        sThumbRY: 0,
-    },
-};
+#[cfg(not(windows))]
+use crate::utils::virtual_keys::*;
+
+#[cfg(windows)]
+pub use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+
+#[cfg(not(windows))]
+pub use crate::utils::virtual_keys::VIRTUAL_KEY;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputType {
@@ -23,7 +16,7 @@ pub enum InputType {
     Down,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MouseButton {
     Left,
     Right,
@@ -32,7 +25,7 @@ pub enum MouseButton {
     Extra2,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GamepadButton {
     Up,
     Down,
@@ -66,152 +59,24 @@ pub enum GamepadAxis {
     TriggerRight,
 }
 
-pub unsafe fn send_key(key: VIRTUAL_KEY, input_type: InputType) {
-    let flags: KEYBD_EVENT_FLAGS = match input_type {
-        InputType::Up => match key {
-            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_KEYUP | KEYEVENTF_EXTENDEDKEY,
-            _ => KEYEVENTF_KEYUP,
-        },
-        InputType::Down => match key {
-            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_EXTENDEDKEY,
-            _ => KEYBD_EVENT_FLAGS(0),
-        },
-    };
-
-    let key_input = INPUT {
-        r#type: INPUT_KEYBOARD,
-        Anonymous: INPUT_0 {
-            ki: KEYBDINPUT {
-                wVk: key,
-                wScan: 0,
-                dwFlags: flags,
-                time: 0,
-                dwExtraInfo: 0,
-            },
-        },
-    };
-
-    SendInput(&[key_input], size_of::<INPUT>() as i32);
-}
-
-pub unsafe fn send_key_raw(key: VIRTUAL_KEY, input_type: InputType) {
-    let flags: KEYBD_EVENT_FLAGS = match input_type {
-        InputType::Up => match key {
-            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => {
-                KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP | KEYEVENTF_EXTENDEDKEY
-            }
-            _ => KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP,
-        },
-        InputType::Down => match key {
-            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_SCANCODE | KEYEVENTF_EXTENDEDKEY,
-            _ => KEYEVENTF_SCANCODE,
-        },
-    };
-
-    let key_input = INPUT {
-        r#type: INPUT_KEYBOARD,
-        Anonymous: INPUT_0 {
-            ki: KEYBDINPUT {
-                wVk: VIRTUAL_KEY(0),
-                wScan: MapVirtualKeyW(key.0 as u32, MAPVK_VK_TO_VSC) as u16,
-                dwFlags: flags,
-                time: 0,
-                dwExtraInfo: 0,
-            },
-        },
-    };
-
-    SendInput(&[key_input], size_of::<INPUT>() as i32);
-}
-
-pub unsafe fn send_mouse_button(button: MouseButton, input_type: InputType) {
-    let mouse_event: MOUSE_EVENT_FLAGS = match button {
-        MouseButton::Left => match input_type {
-            InputType::Up => MOUSEEVENTF_LEFTUP,
-            InputType::Down => MOUSEEVENTF_LEFTDOWN,
-        },
-        MouseButton::Right => match input_type {
-            InputType::Up => MOUSEEVENTF_RIGHTUP,
-            InputType::Down => MOUSEEVENTF_RIGHTDOWN,
-        },
-        MouseButton::Middle => match input_type {
-            InputType::Up => MOUSEEVENTF_MIDDLEUP,
-            InputType::Down => MOUSEEVENTF_MIDDLEDOWN,
-        },
-        MouseButton::Extra1 | MouseButton::Extra2 => match input_type {
-            InputType::Up => MOUSEEVENTF_XUP,
-            InputType::Down => MOUSEEVENTF_XDOWN,
-        },
-    };
-
-    let mouse_data: u32 = match button {
-        MouseButton::Extra1 => XBUTTON1 as u32,
-        MouseButton::Extra2 => XBUTTON2 as u32,
-        _ => 0,
-    };
-
-    let mouse_input = INPUT {
-        r#type: INPUT_MOUSE,
-        Anonymous: INPUT_0 {
-            mi: MOUSEINPUT {
-                dx: 0,
-                dy: 0,
-                mouseData: mouse_data,
-                dwFlags: mouse_event,
-                time: 0,
-                dwExtraInfo: 0,
-            },
-        },
-    };
-
-    SendInput(&[mouse_input], size_of::<INPUT>() as i32);
-}
-
-pub unsafe fn send_mouse_scroll(amount: u32, input_type: InputType) {
-    let scroll_amount: i32 = match input_type {
-        InputType::Up => (WHEEL_DELTA * amount) as i32,
-        InputType::Down => (WHEEL_DELTA * amount) as i32 * -1,
-    };
-
-    let scroll_input = INPUT {
-        r#type: INPUT_MOUSE,
-        Anonymous: INPUT_0 {
-            mi: MOUSEINPUT {
-                dx: 0,
-                dy: 0,
-                mouseData: scroll_amount.cast_unsigned(),
-                dwFlags: MOUSEEVENTF_WHEEL,
-                time: 0,
-                dwExtraInfo: 0,
-            },
-        },
-    };
-
-    SendInput(&[scroll_input], size_of::<INPUT>() as i32);
-}
-
-pub unsafe fn send_mouse_move(x: i32, y: i32) {
-    let move_input = INPUT {
-        r#type: INPUT_MOUSE,
-        Anonymous: INPUT_0 {
-            mi: MOUSEINPUT {
-                dx: x,
-                dy: y,
-                mouseData: 0,
-                dwFlags: MOUSEEVENTF_MOVE,
-                time: 0,
-                dwExtraInfo: 0,
-            },
-        },
-    };
-
-    SendInput(&[move_input], size_of::<INPUT>() as i32);
+// Same layout as XINPUT_STATE, so it can be written straight into the game
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadState {
+    pub packet_number: u32,
+    pub buttons: u16,
+    pub trigger_left: u8,
+    pub trigger_right: u8,
+    pub stick_left_x: i16,
+    pub stick_left_y: i16,
+    pub stick_right_x: i16,
+    pub stick_right_y: i16,
 }
 
-pub unsafe fn send_gamepad_button(button: GamepadButton, input_type: InputType) {
-    XINPUT_STATE_OVERRIDE.dwPacketNumber += 1;
+pub fn set_gamepad_button(state: &mut GamepadState, button: GamepadButton, input_type: InputType) {
+    state.packet_number += 1;
 
-    let mut buttons = XINPUT_STATE_OVERRIDE.Gamepad.wButtons.0;
+    let mut buttons = state.buttons;
 
     if input_type == InputType::Down {
         match button {
@@ -307,30 +172,30 @@ pub unsafe fn send_gamepad_button(button: GamepadButton, input_type: InputType)
         }
     }
 
-    XINPUT_STATE_OVERRIDE.Gamepad.wButtons = XINPUT_GAMEPAD_BUTTON_FLAGS(buttons);
+    state.buttons = buttons;
 }
 
-pub unsafe fn send_gamepad_axis(axis: GamepadAxis, amount: i32) {
-    XINPUT_STATE_OVERRIDE.dwPacketNumber += 1;
+pub fn set_gamepad_axis(state: &mut GamepadState, axis: GamepadAxis, amount: i32) {
+    state.packet_number += 1;
 
     match axis {
         GamepadAxis::StickLeftX => {
-            XINPUT_STATE_OVERRIDE.Gamepad.sThumbLX = amount as i16;
+            state.stick_left_x = amount as i16;
         }
         GamepadAxis::StickLeftY => {
-            XINPUT_STATE_OVERRIDE.Gamepad.sThumbLY = amount as i16;
+            state.stick_left_y = amount as i16;
         }
         GamepadAxis::StickRightX => {
-            XINPUT_STATE_OVERRIDE.Gamepad.sThumbRX = amount as i16;
+            state.stick_right_x = amount as i16;
         }
         GamepadAxis::StickRightY => {
-            XINPUT_STATE_OVERRIDE.Gamepad.sThumbRY = amount as i16;
+            state.stick_right_y = amount as i16;
         }
         GamepadAxis::TriggerLeft => {
-            XINPUT_STATE_OVERRIDE.Gamepad.bLeftTrigger = amount as u8;
+            state.trigger_left = amount as u8;
         }
         GamepadAxis::TriggerRight => {
-            XINPUT_STATE_OVERRIDE.Gamepad.bRightTrigger = amount as u8;
+            state.trigger_right = amount as u8;
         }
         _ => {}
     }
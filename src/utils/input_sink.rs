@@ -0,0 +1,119 @@
+use crate::utils::input::*;
+
+// Where the inputs of a TAS end up, so every game can pick its own way of sending them
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    Key {
+        input_type: InputType,
+        key: VIRTUAL_KEY,
+    },
+    KeyAlternative {
+        input_type: InputType,
+        key: VIRTUAL_KEY,
+    },
+    MouseButton {
+        input_type: InputType,
+        button: MouseButton,
+    },
+    MouseScroll {
+        input_type: InputType,
+        amount: u32,
+    },
+    MouseMove {
+        x: i32,
+        y: i32,
+    },
+    GamepadButton {
+        input_type: InputType,
+        button: GamepadButton,
+    },
+    GamepadAxis {
+        axis: GamepadAxis,
+        amount: i32,
+    },
+}
+
+impl InputEvent {
+    pub fn is_gamepad(&self) -> bool {
+        return matches!(
+            self,
+            InputEvent::GamepadButton { .. } | InputEvent::GamepadAxis { .. }
+        );
+    }
+}
+
+pub trait InputSink {
+    fn send(&mut self, input: InputEvent); // Send an input, or queue it until the end of the frame
+    fn flush(&mut self, frame: u32) {} // End of a TAS frame, after all actions
+}
+
+// Lets a sink be borrowed, to look at it again after the run
+impl<T: InputSink + ?Sized> InputSink for &mut T {
+    fn send(&mut self, input: InputEvent) {
+        (**self).send(input);
+    }
+
+    fn flush(&mut self, frame: u32) {
+        (**self).flush(frame);
+    }
+}
+
+// Sends every input to all sinks in order
+impl<'a> InputSink for Vec<Box<dyn InputSink + 'a>> {
+    fn send(&mut self, input: InputEvent) {
+        for sink in self.iter_mut() {
+            sink.send(input);
+        }
+    }
+
+    fn flush(&mut self, frame: u32) {
+        for sink in self.iter_mut() {
+            sink.flush(frame);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    pub frame: u32,
+    pub inputs: Vec<InputEvent>,
+}
+
+// Only records inputs, to check what a script sends on which frame
+#[derive(Debug, Clone, Default)]
+pub struct RecordingSink {
+    pending: Vec<InputEvent>,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl RecordingSink {
+    pub fn new() -> RecordingSink {
+        return RecordingSink::default();
+    }
+
+    // All inputs of a single TAS frame, empty if nothing was sent
+    pub fn inputs_at(&self, frame: u32) -> Vec<InputEvent> {
+        return self
+            .frames
+            .iter()
+            .filter(|x| x.frame == frame)
+            .flat_map(|x| x.inputs.iter().copied())
+            .collect();
+    }
+}
+
+impl InputSink for RecordingSink {
+    fn send(&mut self, input: InputEvent) {
+        self.pending.push(input);
+    }
+
+    fn flush(&mut self, frame: u32) {
+        if !self.pending.is_empty() {
+            self.frames.push(RecordedFrame {
+                frame: frame,
+                inputs: std::mem::take(&mut self.pending),
+            });
+        }
+    }
+}
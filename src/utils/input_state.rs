@@ -0,0 +1,261 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::utils::actions::*;
+use crate::utils::input::*;
+use crate::utils::input_sink::*;
+
+// Everything the script wants to be held down right now, actions change it and only the difference gets sent
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::StickLeftX,
+    GamepadAxis::StickLeftY,
+    GamepadAxis::StickRightX,
+    GamepadAxis::StickRightY,
+    GamepadAxis::TriggerLeft,
+    GamepadAxis::TriggerRight,
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputState {
+    pub keys: Vec<VIRTUAL_KEY>,
+    pub keys_alternative: Vec<VIRTUAL_KEY>,
+    pub mouse_buttons: Vec<MouseButton>,
+    pub gamepad_buttons: Vec<GamepadButton>,
+    pub gamepad_axes: [i32; 6], // Same order as GAMEPAD_AXES
+}
+
+impl InputState {
+    pub fn new() -> InputState {
+        return InputState::default();
+    }
+
+    // Updates the state with an input, returns false for inputs that aren't held, like mouse movement
+    pub fn apply(&mut self, input: InputEvent) -> bool {
+        match input {
+            InputEvent::Key { input_type, key } => {
+                set_held(&mut self.keys, key, input_type);
+            }
+            InputEvent::KeyAlternative { input_type, key } => {
+                set_held(&mut self.keys_alternative, key, input_type);
+            }
+            InputEvent::MouseButton { input_type, button } => {
+                set_held(&mut self.mouse_buttons, button, input_type);
+            }
+            InputEvent::GamepadButton { input_type, button } => {
+                set_held(&mut self.gamepad_buttons, button, input_type);
+            }
+            InputEvent::GamepadAxis { axis, amount } => {
+                self.gamepad_axes[axis_index(axis)] = amount;
+            }
+            InputEvent::MouseScroll { .. } | InputEvent::MouseMove { .. } => {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    // Whether the button or key of an input is held, axes and mouse movement never are
+    pub fn is_held(&self, input: &InputEvent) -> bool {
+        return match *input {
+            InputEvent::Key { key, .. } => self.keys.contains(&key),
+            InputEvent::KeyAlternative { key, .. } => self.keys_alternative.contains(&key),
+            InputEvent::MouseButton { button, .. } => self.mouse_buttons.contains(&button),
+            InputEvent::GamepadButton { button, .. } => self.gamepad_buttons.contains(&button),
+            _ => false,
+        };
+    }
+
+    // Keyboard and mouse of this state, gamepad of the other one
+    pub fn with_gamepad_of(&self, other: &InputState) -> InputState {
+        return InputState {
+            keys: self.keys.clone(),
+            keys_alternative: self.keys_alternative.clone(),
+            mouse_buttons: self.mouse_buttons.clone(),
+            gamepad_buttons: other.gamepad_buttons.clone(),
+            gamepad_axes: other.gamepad_axes,
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return *self == InputState::default();
+    }
+
+    // Inputs to get from the previous state to this one, releases first
+    pub fn diff(&self, previous: &InputState) -> Vec<InputEvent> {
+        let mut inputs = Vec::new();
+
+        for key in previous.keys.iter().filter(|x| !self.keys.contains(x)) {
+            inputs.push(InputEvent::Key {
+                input_type: InputType::Up,
+                key: *key,
+            });
+        }
+        for key in previous
+            .keys_alternative
+            .iter()
+            .filter(|x| !self.keys_alternative.contains(x))
+        {
+            inputs.push(InputEvent::KeyAlternative {
+                input_type: InputType::Up,
+                key: *key,
+            });
+        }
+        for button in previous
+            .mouse_buttons
+            .iter()
+            .filter(|x| !self.mouse_buttons.contains(x))
+        {
+            inputs.push(InputEvent::MouseButton {
+                input_type: InputType::Up,
+                button: *button,
+            });
+        }
+        for button in previous
+            .gamepad_buttons
+            .iter()
+            .filter(|x| !self.gamepad_buttons.contains(x))
+        {
+            inputs.push(InputEvent::GamepadButton {
+                input_type: InputType::Up,
+                button: *button,
+            });
+        }
+
+        for key in self.keys.iter().filter(|x| !previous.keys.contains(x)) {
+            inputs.push(InputEvent::Key {
+                input_type: InputType::Down,
+                key: *key,
+            });
+        }
+        for key in self
+            .keys_alternative
+            .iter()
+            .filter(|x| !previous.keys_alternative.contains(x))
+        {
+            inputs.push(InputEvent::KeyAlternative {
+                input_type: InputType::Down,
+                key: *key,
+            });
+        }
+        for button in self
+            .mouse_buttons
+            .iter()
+            .filter(|x| !previous.mouse_buttons.contains(x))
+        {
+            inputs.push(InputEvent::MouseButton {
+                input_type: InputType::Down,
+                button: *button,
+            });
+        }
+        for button in self
+            .gamepad_buttons
+            .iter()
+            .filter(|x| !previous.gamepad_buttons.contains(x))
+        {
+            inputs.push(InputEvent::GamepadButton {
+                input_type: InputType::Down,
+                button: *button,
+            });
+        }
+
+        for i in 0..GAMEPAD_AXES.len() {
+            if self.gamepad_axes[i] != previous.gamepad_axes[i] {
+                inputs.push(InputEvent::GamepadAxis {
+                    axis: GAMEPAD_AXES[i],
+                    amount: self.gamepad_axes[i],
+                });
+            }
+        }
+
+        return inputs;
+    }
+
+    // Inputs to set the whole state again, for when the game might have missed some
+    pub fn full(&self) -> Vec<InputEvent> {
+        let mut inputs = self.diff(&InputState::default());
+
+        // Also resend axes at rest, the game could still have them pushed
+        for i in 0..GAMEPAD_AXES.len() {
+            if self.gamepad_axes[i] == 0 {
+                inputs.push(InputEvent::GamepadAxis {
+                    axis: GAMEPAD_AXES[i],
+                    amount: 0,
+                });
+            }
+        }
+
+        return inputs;
+    }
+}
+
+impl Display for InputState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+
+        for key in self.keys.iter().chain(self.keys_alternative.iter()) {
+            parts.push(format!("key {:#04x}", key.0));
+        }
+        for button in self.mouse_buttons.iter() {
+            parts.push(format!("mouse {:?}", button));
+        }
+        for button in self.gamepad_buttons.iter() {
+            parts.push(format!("gamepad {:?}", button));
+        }
+        for i in 0..GAMEPAD_AXES.len() {
+            if self.gamepad_axes[i] != 0 {
+                parts.push(format!("{:?} {}", GAMEPAD_AXES[i], self.gamepad_axes[i]));
+            }
+        }
+
+        if parts.is_empty() {
+            return write!(f, "-");
+        } else {
+            return write!(f, "{}", parts.join(", "));
+        }
+    }
+}
+
+fn set_held<T: PartialEq>(held: &mut Vec<T>, value: T, input_type: InputType) {
+    if input_type == InputType::Down {
+        if !held.contains(&value) {
+            held.push(value);
+        }
+    } else {
+        held.retain(|x| *x != value);
+    }
+}
+
+fn axis_index(axis: GamepadAxis) -> usize {
+    return GAMEPAD_AXES.iter().position(|x| *x == axis).unwrap();
+}
+
+// Held inputs on every frame that has input actions, without running the script (awaits are ignored)
+pub fn input_timeline(script: &TasScript) -> Vec<(u32, InputState)> {
+    let mut timeline: Vec<(u32, InputState)> = Vec::new();
+    let mut input_state = InputState::new();
+
+    let mut actions: Vec<&TasAction> = script
+        .actions
+        .iter()
+        .filter(|x| !x.action.input_events().is_empty())
+        .collect();
+    actions.sort_by_key(|x| x.frame);
+
+    for tas_action in actions {
+        for input in tas_action.action.input_events() {
+            input_state.apply(input);
+        }
+
+        match timeline.last_mut() {
+            Some((frame, state)) if *frame == tas_action.frame => {
+                *state = input_state.clone();
+            }
+            _ => {
+                timeline.push((tas_action.frame, input_state.clone()));
+            }
+        }
+    }
+
+    return timeline;
+}
@@ -0,0 +1,153 @@
+use crate::utils::actions::*;
+use crate::utils::input::*;
+use crate::utils::input_sink::*;
+use crate::utils::input_state::*;
+
+// Finds mistakes in a script that still parses fine, like keys that are never released
+
+#[derive(Debug, Clone)]
+pub struct LintWarning {
+    pub line: usize,
+    pub message: String,
+}
+
+pub fn lint_script(script: &TasScript) -> Vec<LintWarning> {
+    let mut warnings: Vec<LintWarning> = Vec::new();
+    let mut input_state = InputState::new();
+    let mut pressed_at: Vec<(InputEvent, usize)> = Vec::new();
+
+    // Same order the runner goes through them
+    let mut actions: Vec<&TasAction> = script.actions.iter().collect();
+    actions.sort_by_key(|x| x.frame);
+
+    for tas_action in actions {
+        for input in tas_action.action.input_events() {
+            let input_type = match input {
+                InputEvent::Key { input_type, .. }
+                | InputEvent::KeyAlternative { input_type, .. }
+                | InputEvent::MouseButton { input_type, .. }
+                | InputEvent::GamepadButton { input_type, .. } => input_type,
+                _ => {
+                    continue;
+                }
+            };
+
+            let held = input_state.is_held(&input);
+            if input_type == InputType::Down && held {
+                warnings.push(LintWarning {
+                    line: tas_action.line,
+                    message: format!("{} is pressed, but it's already held", describe(&input)),
+                });
+            } else if input_type == InputType::Up && !held {
+                warnings.push(LintWarning {
+                    line: tas_action.line,
+                    message: format!("{} is released, but it isn't held", describe(&input)),
+                });
+            }
+
+            input_state.apply(input);
+            pressed_at.retain(|(x, _)| input_state.is_held(x));
+            if input_type == InputType::Down && !held {
+                pressed_at.push((input, tas_action.line));
+            }
+        }
+    }
+
+    for (input, line) in pressed_at {
+        warnings.push(LintWarning {
+            line: line,
+            message: format!("{} is still held when the script ends", describe(&input)),
+        });
+    }
+
+    warnings.sort_by_key(|x| x.line);
+    return warnings;
+}
+
+fn describe(input: &InputEvent) -> String {
+    return match input {
+        InputEvent::Key { key, .. } => format!("Key {:#04x}", key.0),
+        InputEvent::KeyAlternative { key, .. } => format!("Key {:#04x} (alternative)", key.0),
+        InputEvent::MouseButton { button, .. } => format!("Mouse button {:?}", button),
+        InputEvent::GamepadButton { button, .. } => format!("Gamepad button {:?}", button),
+        _ => format!("{:?}", input),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(script: &str) -> Vec<(usize, String)> {
+        return lint_script(&parse_script(script).unwrap())
+            .into_iter()
+            .map(|x| (x.line, x.message))
+            .collect();
+    }
+
+    #[test]
+    fn clean_script_has_no_warnings() {
+        let warnings = lint(
+            "0 key down w\n\
+             10 key up w\n\
+             10 gamepad button down a\n\
+             12 gamepad button up a",
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn held_and_released_twice() {
+        let warnings = lint(
+            "0 mouse button down left\n\
+             5 mouse button down left\n\
+             6 mouse button up left\n\
+             7 mouse button up left",
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    2,
+                    String::from("Mouse button Left is pressed, but it's already held")
+                ),
+                (
+                    4,
+                    String::from("Mouse button Left is released, but it isn't held")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn held_at_the_end() {
+        let warnings = lint(
+            "0 key down w\n\
+             0 gamepad button down a\n\
+             5 gamepad button up a",
+        );
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert_eq!(warnings[0].0, 1);
+        assert!(
+            warnings[0]
+                .1
+                .ends_with("is still held when the script ends")
+        );
+    }
+
+    #[test]
+    fn actions_are_checked_in_frame_order() {
+        // Released on a later line, but an earlier frame
+        let warnings = lint(
+            "10 key down w\n\
+             5 key up w",
+        );
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(
+            warnings[0]
+                .1
+                .ends_with("is still held when the script ends")
+        );
+        assert!(warnings[1].1.ends_with("is released, but it isn't held"));
+    }
+}
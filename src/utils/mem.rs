@@ -3,7 +3,9 @@ use std::os::raw::c_void;
 use std::env;
 use std::path::PathBuf;
 
-use windows::Win32::Foundation::{HWND, LPARAM};
+use windows::Win32::Foundation::{CloseHandle, HWND, LPARAM};
+use windows::Win32::System::ProcessStatus::K32EnumProcesses;
+use windows::Win32::System::Threading::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use windows_core::BOOL;
@@ -15,9 +17,32 @@ pub struct ModuleExport {
     pub addr: usize,
 }
 
+pub struct RunningProcess {
+    pub id: u32,
+    pub name: String,
+    pub is_32bit: bool,
+}
+
 struct WindowData {
     id: u32,
     hwnd: HWND,
+    hwnd_fallback: HWND,
+}
+
+// Folder with the DLLs to inject, next to the TAS tool unless set otherwise
+static mut MODULE_DIR: Option<PathBuf> = None;
+
+pub unsafe fn set_module_dir(dir: PathBuf) {
+    MODULE_DIR = Some(dir);
+}
+
+pub unsafe fn get_module_dir() -> PathBuf {
+    if let Some(dir) = MODULE_DIR.clone() {
+        return dir;
+    }
+
+    let exe_path = env::current_exe().unwrap();
+    return PathBuf::from(exe_path).parent().unwrap().to_path_buf();
 }
 
 pub unsafe fn get_module(process: &mut Process, module_name: &str) -> Option<ProcessModule> {
@@ -35,12 +60,14 @@ pub unsafe fn get_or_inject_module(
     if let Some(module_existing) = get_module(process, module_name) {
         return Some(module_existing);
     } else {
-        let exe_path = env::current_exe().unwrap();
-        let module_path = PathBuf::from(exe_path).parent().unwrap().join(module_name);
+        let module_path = get_module_dir().join(module_name);
 
-        process
+        if process
             .inject_dll(module_path.into_os_string().to_str().unwrap())
-            .expect("Failed to inject module!");
+            .is_err()
+        {
+            return None;
+        }
 
         if let Some(module_injected) = get_module(process, module_name) {
             return Some(module_injected);
@@ -64,10 +91,13 @@ pub unsafe fn get_exports(module: ProcessModule) -> Vec<ModuleExport> {
     return exports;
 }
 
+// Main window of a process: visible, top-level and not owned by another window.
+// Falls back to any window of the process, as IME or console windows get enumerated too.
 pub unsafe fn get_hwnd_by_id(process_id: u32) -> HWND {
     let mut window_data = Box::new(WindowData {
         id: process_id,
         hwnd: HWND(0 as *mut c_void),
+        hwnd_fallback: HWND(0 as *mut c_void),
     });
 
     let window_data_ptr: *mut WindowData = &mut *window_data;
@@ -77,9 +107,18 @@ pub unsafe fn get_hwnd_by_id(process_id: u32) -> HWND {
         LPARAM(window_data_ptr as isize),
     );
 
+    if window_data.hwnd.is_invalid() {
+        return window_data.hwnd_fallback;
+    }
+
     return window_data.hwnd;
 }
 
+// Whether the process has opened its main window yet
+pub unsafe fn has_main_window(process_id: u32) -> bool {
+    return is_main_window(get_hwnd_by_id(process_id));
+}
+
 #[allow(unused_mut)]
 unsafe extern "system" fn get_hwnd_by_id_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
     let mut window_data: &mut WindowData = &mut *(lparam.0 as *mut WindowData);
@@ -87,10 +126,81 @@ unsafe extern "system" fn get_hwnd_by_id_callback(hwnd: HWND, lparam: LPARAM) ->
     let mut window_id: u32 = 0;
     GetWindowThreadProcessId(hwnd, Some(&mut window_id));
 
-    if window_id == window_data.id.try_into().unwrap() {
+    if window_id != window_data.id {
+        return BOOL(1);
+    }
+
+    if window_data.hwnd_fallback.is_invalid() {
+        window_data.hwnd_fallback = hwnd;
+    }
+
+    if is_main_window(hwnd) {
         window_data.hwnd = hwnd;
         return BOOL(0);
     }
 
     return BOOL(1);
 }
+
+unsafe fn is_main_window(hwnd: HWND) -> bool {
+    let has_owner = GetWindow(hwnd, GW_OWNER).is_ok_and(|x| !x.is_invalid());
+    let is_tool_window = (GetWindowLongW(hwnd, GWL_EXSTYLE) as u32 & WS_EX_TOOLWINDOW.0) != 0;
+
+    return IsWindowVisible(hwnd).as_bool() && !has_owner && !is_tool_window;
+}
+
+// All processes that can be queried, others (like system processes) are skipped
+pub unsafe fn get_running_processes() -> Vec<RunningProcess> {
+    let mut ids: Vec<u32> = vec![0; 4096];
+    let mut bytes_needed: u32 = 0;
+
+    if !K32EnumProcesses(
+        ids.as_mut_ptr(),
+        (ids.len() * size_of::<u32>()) as u32,
+        &mut bytes_needed,
+    )
+    .as_bool()
+    {
+        return Vec::new();
+    }
+    ids.truncate(bytes_needed as usize / size_of::<u32>());
+
+    let mut processes: Vec<RunningProcess> = Vec::new();
+    for id in ids {
+        let handle = match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, id) {
+            Ok(x) => x,
+            Err(_) => continue,
+        };
+
+        let mut path: Vec<u16> = vec![0; 1024];
+        let mut path_len: u32 = path.len() as u32;
+        let name_result = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            windows_core::PWSTR(path.as_mut_ptr()),
+            &mut path_len,
+        );
+
+        // 32-bit processes run under WOW64 on 64-bit Windows
+        let mut is_wow64 = BOOL(0);
+        let wow64_result = IsWow64Process(handle, &mut is_wow64);
+
+        let _ = CloseHandle(handle);
+
+        if name_result.is_err() || wow64_result.is_err() {
+            continue;
+        }
+
+        let path = String::from_utf16_lossy(&path[..path_len as usize]);
+        processes.push(RunningProcess {
+            id: id,
+            name: PathBuf::from(path)
+                .file_name()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            is_32bit: is_wow64.as_bool(),
+        });
+    }
+
+    return processes;
+}
@@ -1,4 +1,20 @@
-pub(crate) mod actions;
-pub(crate) mod input;
-pub(crate) mod mem;
-pub(crate) mod version;
+pub mod abort;
+pub mod actions;
+pub mod format;
+pub mod geometry;
+pub mod input;
+pub mod input_sink;
+pub mod input_state;
+pub mod lint;
+
+// Shared with soulstas-patches and soulstas-sigcheck
+pub use soulstas_shared::version;
+
+#[cfg(windows)]
+pub mod mem;
+
+#[cfg(windows)]
+pub mod send_input;
+
+#[cfg(not(windows))]
+pub mod virtual_keys;
@@ -0,0 +1,224 @@
+use std::mem::*;
+
+use mem_rs::prelude::*;
+
+use windows::Win32::UI::Input::KeyboardAndMouse::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+use crate::utils::input::*;
+use crate::utils::input_sink::*;
+
+// Sending inputs to the system and into the game's memory, only on Windows
+
+pub unsafe fn send_key(key: VIRTUAL_KEY, input_type: InputType) {
+    let flags: KEYBD_EVENT_FLAGS = match input_type {
+        InputType::Up => match key {
+            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_KEYUP | KEYEVENTF_EXTENDEDKEY,
+            _ => KEYEVENTF_KEYUP,
+        },
+        InputType::Down => match key {
+            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_EXTENDEDKEY,
+            _ => KEYBD_EVENT_FLAGS(0),
+        },
+    };
+
+    let key_input = INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: key,
+                wScan: 0,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+
+    SendInput(&[key_input], size_of::<INPUT>() as i32);
+}
+
+pub unsafe fn send_key_raw(key: VIRTUAL_KEY, input_type: InputType) {
+    let flags: KEYBD_EVENT_FLAGS = match input_type {
+        InputType::Up => match key {
+            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => {
+                KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP | KEYEVENTF_EXTENDEDKEY
+            }
+            _ => KEYEVENTF_SCANCODE | KEYEVENTF_KEYUP,
+        },
+        InputType::Down => match key {
+            VK_UP | VK_DOWN | VK_LEFT | VK_RIGHT => KEYEVENTF_SCANCODE | KEYEVENTF_EXTENDEDKEY,
+            _ => KEYEVENTF_SCANCODE,
+        },
+    };
+
+    let key_input = INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: VIRTUAL_KEY(0),
+                wScan: MapVirtualKeyW(key.0 as u32, MAPVK_VK_TO_VSC) as u16,
+                dwFlags: flags,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+
+    SendInput(&[key_input], size_of::<INPUT>() as i32);
+}
+
+pub unsafe fn send_mouse_button(button: MouseButton, input_type: InputType) {
+    let mouse_event: MOUSE_EVENT_FLAGS = match button {
+        MouseButton::Left => match input_type {
+            InputType::Up => MOUSEEVENTF_LEFTUP,
+            InputType::Down => MOUSEEVENTF_LEFTDOWN,
+        },
+        MouseButton::Right => match input_type {
+            InputType::Up => MOUSEEVENTF_RIGHTUP,
+            InputType::Down => MOUSEEVENTF_RIGHTDOWN,
+        },
+        MouseButton::Middle => match input_type {
+            InputType::Up => MOUSEEVENTF_MIDDLEUP,
+            InputType::Down => MOUSEEVENTF_MIDDLEDOWN,
+        },
+        MouseButton::Extra1 | MouseButton::Extra2 => match input_type {
+            InputType::Up => MOUSEEVENTF_XUP,
+            InputType::Down => MOUSEEVENTF_XDOWN,
+        },
+    };
+
+    let mouse_data: u32 = match button {
+        MouseButton::Extra1 => XBUTTON1 as u32,
+        MouseButton::Extra2 => XBUTTON2 as u32,
+        _ => 0,
+    };
+
+    let mouse_input = INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx: 0,
+                dy: 0,
+                mouseData: mouse_data,
+                dwFlags: mouse_event,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+
+    SendInput(&[mouse_input], size_of::<INPUT>() as i32);
+}
+
+pub unsafe fn send_mouse_scroll(amount: u32, input_type: InputType) {
+    let scroll_amount: i32 = match input_type {
+        InputType::Up => (WHEEL_DELTA * amount) as i32,
+        InputType::Down => (WHEEL_DELTA * amount) as i32 * -1,
+    };
+
+    let scroll_input = INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx: 0,
+                dy: 0,
+                mouseData: scroll_amount.cast_unsigned(),
+                dwFlags: MOUSEEVENTF_WHEEL,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+
+    SendInput(&[scroll_input], size_of::<INPUT>() as i32);
+}
+
+pub unsafe fn send_mouse_move(x: i32, y: i32) {
+    let move_input = INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                dx: x,
+                dy: y,
+                mouseData: 0,
+                dwFlags: MOUSEEVENTF_MOVE,
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    };
+
+    SendInput(&[move_input], size_of::<INPUT>() as i32);
+}
+
+// Keyboard and mouse through SendInput, goes to whatever window is focused
+pub struct SendInputSink;
+
+impl InputSink for SendInputSink {
+    fn send(&mut self, input: InputEvent) {
+        match input {
+            InputEvent::Key { input_type, key } => unsafe {
+                send_key_raw(key, input_type);
+            },
+            InputEvent::KeyAlternative { input_type, key } => unsafe {
+                send_key(key, input_type);
+            },
+            InputEvent::MouseButton { input_type, button } => unsafe {
+                send_mouse_button(button, input_type);
+            },
+            InputEvent::MouseScroll { input_type, amount } => unsafe {
+                send_mouse_scroll(amount, input_type);
+            },
+            InputEvent::MouseMove { x, y } => unsafe {
+                send_mouse_move(x, y);
+            },
+            _ => {}
+        }
+    }
+}
+
+// Gamepad straight into the XInput state the injected hook returns to the game, written once per frame
+pub struct HookSink {
+    xinput_state: Pointer,
+    state: GamepadState,
+}
+
+impl HookSink {
+    pub fn new(xinput_state: Pointer) -> HookSink {
+        return HookSink {
+            xinput_state: xinput_state,
+            state: GamepadState::default(),
+        };
+    }
+}
+
+impl InputSink for HookSink {
+    fn send(&mut self, input: InputEvent) {
+        match input {
+            InputEvent::GamepadButton { input_type, button } => {
+                set_gamepad_button(&mut self.state, button, input_type);
+            }
+            InputEvent::GamepadAxis { axis, amount } => {
+                set_gamepad_axis(&mut self.state, axis, amount);
+            }
+            _ => {}
+        }
+    }
+
+    fn flush(&mut self, frame: u32) {
+        let state_buf = unsafe {
+            &*(&self.state as *const GamepadState
+                as *const [u8; core::mem::size_of::<GamepadState>()])
+        };
+        self.xinput_state.write_memory_rel(None, state_buf);
+    }
+}
+
+// SendInput for keyboard and mouse, the injected hook for the gamepad
+pub fn hook_input_sink(xinput_state: Pointer) -> Box<dyn InputSink> {
+    return Box::new(vec![
+        Box::new(SendInputSink) as Box<dyn InputSink>,
+        Box::new(HookSink::new(xinput_state)),
+    ]);
+}
@@ -0,0 +1,70 @@
+// Windows virtual key codes with the same values, so scripts can be parsed and simulated on other systems
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VIRTUAL_KEY(pub u16);
+
+pub const VK_BACK: VIRTUAL_KEY = VIRTUAL_KEY(0x08);
+pub const VK_TAB: VIRTUAL_KEY = VIRTUAL_KEY(0x09);
+pub const VK_RETURN: VIRTUAL_KEY = VIRTUAL_KEY(0x0D);
+pub const VK_CAPITAL: VIRTUAL_KEY = VIRTUAL_KEY(0x14);
+pub const VK_ESCAPE: VIRTUAL_KEY = VIRTUAL_KEY(0x1B);
+pub const VK_SPACE: VIRTUAL_KEY = VIRTUAL_KEY(0x20);
+pub const VK_LEFT: VIRTUAL_KEY = VIRTUAL_KEY(0x25);
+pub const VK_UP: VIRTUAL_KEY = VIRTUAL_KEY(0x26);
+pub const VK_RIGHT: VIRTUAL_KEY = VIRTUAL_KEY(0x27);
+pub const VK_DOWN: VIRTUAL_KEY = VIRTUAL_KEY(0x28);
+pub const VK_0: VIRTUAL_KEY = VIRTUAL_KEY(0x30);
+pub const VK_1: VIRTUAL_KEY = VIRTUAL_KEY(0x31);
+pub const VK_2: VIRTUAL_KEY = VIRTUAL_KEY(0x32);
+pub const VK_3: VIRTUAL_KEY = VIRTUAL_KEY(0x33);
+pub const VK_4: VIRTUAL_KEY = VIRTUAL_KEY(0x34);
+pub const VK_5: VIRTUAL_KEY = VIRTUAL_KEY(0x35);
+pub const VK_6: VIRTUAL_KEY = VIRTUAL_KEY(0x36);
+pub const VK_7: VIRTUAL_KEY = VIRTUAL_KEY(0x37);
+pub const VK_8: VIRTUAL_KEY = VIRTUAL_KEY(0x38);
+pub const VK_9: VIRTUAL_KEY = VIRTUAL_KEY(0x39);
+pub const VK_A: VIRTUAL_KEY = VIRTUAL_KEY(0x41);
+pub const VK_B: VIRTUAL_KEY = VIRTUAL_KEY(0x42);
+pub const VK_C: VIRTUAL_KEY = VIRTUAL_KEY(0x43);
+pub const VK_D: VIRTUAL_KEY = VIRTUAL_KEY(0x44);
+pub const VK_E: VIRTUAL_KEY = VIRTUAL_KEY(0x45);
+pub const VK_F: VIRTUAL_KEY = VIRTUAL_KEY(0x46);
+pub const VK_G: VIRTUAL_KEY = VIRTUAL_KEY(0x47);
+pub const VK_H: VIRTUAL_KEY = VIRTUAL_KEY(0x48);
+pub const VK_I: VIRTUAL_KEY = VIRTUAL_KEY(0x49);
+pub const VK_J: VIRTUAL_KEY = VIRTUAL_KEY(0x4A);
+pub const VK_K: VIRTUAL_KEY = VIRTUAL_KEY(0x4B);
+pub const VK_L: VIRTUAL_KEY = VIRTUAL_KEY(0x4C);
+pub const VK_M: VIRTUAL_KEY = VIRTUAL_KEY(0x4D);
+pub const VK_N: VIRTUAL_KEY = VIRTUAL_KEY(0x4E);
+pub const VK_O: VIRTUAL_KEY = VIRTUAL_KEY(0x4F);
+pub const VK_P: VIRTUAL_KEY = VIRTUAL_KEY(0x50);
+pub const VK_Q: VIRTUAL_KEY = VIRTUAL_KEY(0x51);
+pub const VK_R: VIRTUAL_KEY = VIRTUAL_KEY(0x52);
+pub const VK_S: VIRTUAL_KEY = VIRTUAL_KEY(0x53);
+pub const VK_T: VIRTUAL_KEY = VIRTUAL_KEY(0x54);
+pub const VK_U: VIRTUAL_KEY = VIRTUAL_KEY(0x55);
+pub const VK_V: VIRTUAL_KEY = VIRTUAL_KEY(0x56);
+pub const VK_W: VIRTUAL_KEY = VIRTUAL_KEY(0x57);
+pub const VK_X: VIRTUAL_KEY = VIRTUAL_KEY(0x58);
+pub const VK_Y: VIRTUAL_KEY = VIRTUAL_KEY(0x59);
+pub const VK_Z: VIRTUAL_KEY = VIRTUAL_KEY(0x5A);
+pub const VK_F1: VIRTUAL_KEY = VIRTUAL_KEY(0x70);
+pub const VK_F2: VIRTUAL_KEY = VIRTUAL_KEY(0x71);
+pub const VK_F3: VIRTUAL_KEY = VIRTUAL_KEY(0x72);
+pub const VK_F4: VIRTUAL_KEY = VIRTUAL_KEY(0x73);
+pub const VK_F5: VIRTUAL_KEY = VIRTUAL_KEY(0x74);
+pub const VK_F6: VIRTUAL_KEY = VIRTUAL_KEY(0x75);
+pub const VK_F7: VIRTUAL_KEY = VIRTUAL_KEY(0x76);
+pub const VK_F8: VIRTUAL_KEY = VIRTUAL_KEY(0x77);
+pub const VK_F9: VIRTUAL_KEY = VIRTUAL_KEY(0x78);
+pub const VK_F10: VIRTUAL_KEY = VIRTUAL_KEY(0x79);
+pub const VK_F11: VIRTUAL_KEY = VIRTUAL_KEY(0x7A);
+pub const VK_F12: VIRTUAL_KEY = VIRTUAL_KEY(0x7B);
+pub const VK_LSHIFT: VIRTUAL_KEY = VIRTUAL_KEY(0xA0);
+pub const VK_RSHIFT: VIRTUAL_KEY = VIRTUAL_KEY(0xA1);
+pub const VK_LCONTROL: VIRTUAL_KEY = VIRTUAL_KEY(0xA2);
+pub const VK_RCONTROL: VIRTUAL_KEY = VIRTUAL_KEY(0xA3);
+pub const VK_LMENU: VIRTUAL_KEY = VIRTUAL_KEY(0xA4);
+pub const VK_RMENU: VIRTUAL_KEY = VIRTUAL_KEY(0xA5);
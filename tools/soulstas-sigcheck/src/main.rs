@@ -0,0 +1,210 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+
+// Checks the AoB signatures of the TAS tool and soulstas-patches against executables on disk,
+// to see if a game update breaks anything before launching it. Doesn't need Windows, so it runs anywhere.
+
+mod pe;
+
+use std::path::Path;
+use std::{env, process};
+
+use soulstas::games::registry::*;
+use soulstas_shared::signatures::*;
+
+use crate::pe::*;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.len() < 2 {
+        println!("Usage: soulstas-sigcheck (game) (executable)...");
+        println!("Games: {}", game_names().join(", "));
+        process::exit(2);
+    }
+
+    let game_type = match string_to_game(&args[0]) {
+        Some(x) => x.game_type,
+        None => {
+            println!(
+                "Unknown game {}, use one of: {}",
+                args[0],
+                game_names().join(", ")
+            );
+            process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for path in args[1..].iter() {
+        match check_executable(game_type, Path::new(path)) {
+            Ok(true) => {}
+            Ok(false) => failed = true,
+            Err(err) => {
+                println!("{}: {}", path, err);
+                process::exit(2);
+            }
+        }
+    }
+
+    process::exit(if failed { 1 } else { 0 });
+}
+
+// Prints a table of all signatures, returns if all of them were found
+fn check_executable(game_type: GameType, path: &Path) -> Result<bool, String> {
+    let pe_image = PeImage::load(path)?;
+    let version = pe_image.version();
+
+    println!(
+        "{}: {}, version {}",
+        path.display(),
+        if pe_image.is_64_bit { "x64" } else { "x86" },
+        version
+    );
+    println!(
+        "{:<24} {:<8} {:<8} {:<28} Status",
+        "Signature", "Source", "Matches", "Target"
+    );
+
+    let signatures = game_signatures(game_type, &version);
+    let mut failed_count = 0;
+
+    for signature in signatures.iter() {
+        let pattern = string_to_pattern(&signature.pattern)?;
+        let matches = pe_image.scan(&pattern);
+
+        // mem-rs always uses the first match
+        let target = matches
+            .first()
+            .and_then(|x| signature_target(&pe_image, signature, *x));
+        let section = target.and_then(|x| pe_image.section_at(x));
+
+        let status = match (matches.len(), target, section) {
+            (0, _, _) => "FAIL",
+            (_, None, _) | (_, _, None) => "FAIL (target outside of the executable)",
+            (1, _, _) => "OK",
+            _ => "OK (first match used)",
+        };
+        if status.starts_with("FAIL") {
+            failed_count += 1;
+        }
+
+        let target_text = match (target, section) {
+            (Some(x), Some(section)) => {
+                format!("0x{:x} ({})", pe_image.image_base + x as u64, section.name)
+            }
+            _ => String::from("-"),
+        };
+        let source = match signature.source {
+            SignatureSource::Tool => "tool",
+            SignatureSource::Patches => "patches",
+        };
+
+        println!(
+            "{:<24} {:<8} {:<8} {:<28} {}",
+            signature.name,
+            source,
+            matches.len(),
+            target_text,
+            status
+        );
+    }
+
+    println!(
+        "{} of {} signatures found.\n",
+        signatures.len() - failed_count,
+        signatures.len()
+    );
+    return Ok(failed_count == 0);
+}
+
+// RVA the signature leads to: the global a pointer starts at, or the function for patches
+fn signature_target(pe_image: &PeImage, signature: &Signature, found: usize) -> Option<usize> {
+    let address = found + signature.scan_offset;
+
+    return match signature.scan_type {
+        // Relative to the end of the instruction
+        ScanType::Relative { instruction_size } => {
+            let displacement = pe_image.read_u32(address)? as i32 as i64;
+            usize::try_from(found as i64 + instruction_size as i64 + displacement).ok()
+        }
+        // The instruction holds the absolute address of the pointer
+        ScanType::Absolute if signature.offsets.len() > 1 => {
+            let pointer = pe_image.read_pointer(address + signature.offsets[0])?;
+            pointer.checked_sub(pe_image.image_base).map(|x| x as usize)
+        }
+        ScanType::Absolute => Some(address),
+    };
+}
+
+// Every name the TAS tool takes for each game
+fn game_names() -> Vec<String> {
+    return GAMES.iter().map(|x| x.aliases.join("/")).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pe::tests::build_pe;
+
+    fn signature(scan_type: ScanType, scan_offset: usize, offsets: Vec<usize>) -> Signature {
+        return Signature {
+            name: String::from("test"),
+            source: SignatureSource::Tool,
+            pattern: String::new(),
+            scan_type: scan_type,
+            scan_offset: scan_offset,
+            offsets: offsets,
+        };
+    }
+
+    #[test]
+    fn relative_target() {
+        // mov rax, [rip + 0x1ff9] at 0x1000 reads 0x3000, 7 bytes after the start plus the displacement
+        let mut code = vec![0x90; 0x10];
+        code[0..3].copy_from_slice(&[0x48, 0x8b, 0x05]);
+        code[3..7].copy_from_slice(&0x1ff9i32.to_le_bytes());
+        let data = build_pe(
+            true,
+            0x140000000,
+            &[(".text", 0x1000, code), (".data", 0x3000, vec![0; 8])],
+        );
+        let pe_image = PeImage::parse(&data).unwrap();
+
+        let found = pe_image.scan(&string_to_pattern("48 8b 05").unwrap())[0];
+        let signature = signature(
+            ScanType::Relative {
+                instruction_size: 7,
+            },
+            3,
+            vec![0, 0x10],
+        );
+        assert_eq!(signature_target(&pe_image, &signature, found), Some(0x3000));
+    }
+
+    #[test]
+    fn absolute_target() {
+        // mov eax, [0x403000] at 0x1000, the pointer is read from the image base on
+        let mut code = vec![0x90; 0x10];
+        code[0] = 0xa1;
+        code[1..5].copy_from_slice(&0x403000u32.to_le_bytes());
+        let data = build_pe(
+            false,
+            0x400000,
+            &[(".text", 0x1000, code), (".data", 0x3000, vec![0; 8])],
+        );
+        let pe_image = PeImage::parse(&data).unwrap();
+
+        let found = pe_image.scan(&string_to_pattern("a1").unwrap())[0];
+        let signature = signature(ScanType::Absolute, 1, vec![0, 0x10]);
+        assert_eq!(signature_target(&pe_image, &signature, found), Some(0x3000));
+
+        // Patches point at the function itself
+        let signature = signature_for_patch();
+        assert_eq!(signature_target(&pe_image, &signature, found), Some(0x1000));
+    }
+
+    fn signature_for_patch() -> Signature {
+        return signature(ScanType::Absolute, 0, Vec::new());
+    }
+}
@@ -0,0 +1,310 @@
+use std::fs;
+use std::path::Path;
+
+use soulstas_shared::version::*;
+
+// Loads an executable from disk and lays it out like Windows would in memory,
+// so AoB scans and addresses work the same as on the running game.
+
+pub struct PeImage {
+    pub is_64_bit: bool,
+    pub image_base: u64,
+    pub image: Vec<u8>, // Indexed by RVA
+    pub sections: Vec<PeSection>,
+}
+
+pub struct PeSection {
+    pub name: String,
+    pub address: usize, // RVA
+    pub size: usize,
+}
+
+impl PeImage {
+    pub fn load(path: &Path) -> Result<PeImage, String> {
+        let data = fs::read(path).map_err(|err| err.to_string())?;
+        return PeImage::parse(&data);
+    }
+
+    pub fn parse(data: &[u8]) -> Result<PeImage, String> {
+        if data.get(0..2) != Some(b"MZ") {
+            return Err(String::from("Not an executable, MZ header missing"));
+        }
+
+        let pe_offset = read_u32(data, 0x3c).ok_or("File too short")? as usize;
+        if data.get(pe_offset..pe_offset + 4) != Some(b"PE\0\0") {
+            return Err(String::from("PE header missing"));
+        }
+
+        // COFF header
+        let coff = pe_offset + 4;
+        let section_count = read_u16(data, coff + 2).ok_or("File too short")? as usize;
+        let optional_size = read_u16(data, coff + 16).ok_or("File too short")? as usize;
+
+        // Optional header, the 64-bit one has a bigger image base
+        let optional = coff + 20;
+        let is_64_bit = match read_u16(data, optional) {
+            Some(0x10b) => false,
+            Some(0x20b) => true,
+            _ => return Err(String::from("Unknown optional header")),
+        };
+        let image_base = match is_64_bit {
+            true => read_u64(data, optional + 24),
+            false => read_u32(data, optional + 28).map(|x| x as u64),
+        }
+        .ok_or("File too short")?;
+        let image_size = read_u32(data, optional + 56).ok_or("File too short")? as usize;
+        let headers_size = read_u32(data, optional + 60).ok_or("File too short")? as usize;
+
+        let mut image = vec![0u8; image_size];
+        let headers_size = headers_size.min(data.len()).min(image_size);
+        image[..headers_size].copy_from_slice(&data[..headers_size]);
+
+        // Copy every section to its address
+        let mut sections: Vec<PeSection> = Vec::new();
+        for i in 0..section_count {
+            let header = optional + optional_size + i * 40;
+            let name_bytes = data
+                .get(header..header + 8)
+                .ok_or("Section table cut off")?;
+            let name = String::from_utf8_lossy(name_bytes)
+                .trim_end_matches('\0')
+                .to_string();
+
+            let virtual_size = read_u32(data, header + 8).ok_or("File too short")? as usize;
+            let address = read_u32(data, header + 12).ok_or("File too short")? as usize;
+            let raw_size = read_u32(data, header + 16).ok_or("File too short")? as usize;
+            let raw_offset = read_u32(data, header + 20).ok_or("File too short")? as usize;
+
+            // Sections can be bigger in memory than in the file, the rest stays zero
+            let size = if virtual_size == 0 {
+                raw_size
+            } else {
+                virtual_size
+            };
+            let copy_size = raw_size
+                .min(size)
+                .min(data.len().saturating_sub(raw_offset))
+                .min(image_size.saturating_sub(address));
+            if copy_size > 0 {
+                image[address..address + copy_size]
+                    .copy_from_slice(&data[raw_offset..raw_offset + copy_size]);
+            }
+
+            sections.push(PeSection {
+                name: name,
+                address: address,
+                size: size,
+            });
+        }
+
+        return Ok(PeImage {
+            is_64_bit: is_64_bit,
+            image_base: image_base,
+            image: image,
+            sections: sections,
+        });
+    }
+
+    // File version from the version resource, like Windows shows it in the file properties
+    pub fn version(&self) -> Version {
+        // VS_FIXEDFILEINFO starts with this signature
+        let signature = [0xbd, 0x04, 0xef, 0xfe];
+
+        let position = match self.image.windows(4).position(|x| x == signature) {
+            Some(x) => x,
+            None => return Version::default(),
+        };
+        let (version_ms, version_ls) = match (
+            read_u32(&self.image, position + 8),
+            read_u32(&self.image, position + 12),
+        ) {
+            (Some(ms), Some(ls)) => (ms, ls),
+            _ => return Version::default(),
+        };
+
+        return Version {
+            major: (version_ms >> 16) as u16,
+            minor: version_ms as u16,
+            build: (version_ls >> 16) as u16,
+            revision: version_ls as u16,
+        };
+    }
+
+    pub fn section_at(&self, address: usize) -> Option<&PeSection> {
+        return self
+            .sections
+            .iter()
+            .find(|x| address >= x.address && address < x.address + x.size);
+    }
+
+    pub fn read_u32(&self, address: usize) -> Option<u32> {
+        return read_u32(&self.image, address);
+    }
+
+    // Pointer sized value, for absolute addresses in 32-bit code
+    pub fn read_pointer(&self, address: usize) -> Option<u64> {
+        return match self.is_64_bit {
+            true => read_u64(&self.image, address),
+            false => read_u32(&self.image, address).map(|x| x as u64),
+        };
+    }
+
+    // Every place the pattern matches, mem-rs always uses the first one
+    pub fn scan(&self, pattern: &[Option<u8>]) -> Vec<usize> {
+        if pattern.is_empty() || pattern.len() > self.image.len() {
+            return Vec::new();
+        }
+
+        return self
+            .image
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, bytes)| {
+                bytes
+                    .iter()
+                    .zip(pattern.iter())
+                    .all(|(b, p)| p.is_none_or(|p| p == *b))
+            })
+            .map(|(i, _)| i)
+            .collect();
+    }
+}
+
+// Same format as mem-rs, hex bytes with ? (or ??) for wildcards
+pub fn string_to_pattern(pattern: &str) -> Result<Vec<Option<u8>>, String> {
+    let mut bytes: Vec<Option<u8>> = Vec::new();
+
+    for part in pattern.split_whitespace() {
+        match part {
+            "?" | "??" => bytes.push(None),
+            _ => match u8::from_str_radix(part, 16) {
+                Ok(x) => bytes.push(Some(x)),
+                Err(_) => return Err(format!("Invalid byte {} in pattern", part)),
+            },
+        }
+    }
+
+    return Ok(bytes);
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    return data
+        .get(offset..offset + 2)
+        .map(|x| u16::from_le_bytes([x[0], x[1]]));
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    return data
+        .get(offset..offset + 4)
+        .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]));
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    return data
+        .get(offset..offset + 8)
+        .map(|x| u64::from_le_bytes(x.try_into().unwrap()));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Smallest executable the parser accepts, sections are (name, RVA, contents)
+    pub fn build_pe(
+        is_64_bit: bool,
+        image_base: u64,
+        sections: &[(&str, usize, Vec<u8>)],
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; 0x200];
+        data[0..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+
+        let coff = 0x44;
+        let optional = coff + 20;
+        let optional_size: usize = if is_64_bit { 0xf0 } else { 0xe0 };
+        data[coff + 2..coff + 4].copy_from_slice(&(sections.len() as u16).to_le_bytes());
+        data[coff + 16..coff + 18].copy_from_slice(&(optional_size as u16).to_le_bytes());
+
+        if is_64_bit {
+            data[optional..optional + 2].copy_from_slice(&0x20bu16.to_le_bytes());
+            data[optional + 24..optional + 32].copy_from_slice(&image_base.to_le_bytes());
+        } else {
+            data[optional..optional + 2].copy_from_slice(&0x10bu16.to_le_bytes());
+            data[optional + 28..optional + 32].copy_from_slice(&(image_base as u32).to_le_bytes());
+        }
+
+        let image_size = sections
+            .iter()
+            .map(|(_, address, contents)| (address + contents.len()).next_multiple_of(0x1000))
+            .max()
+            .unwrap_or(0x1000);
+        data[optional + 56..optional + 60].copy_from_slice(&(image_size as u32).to_le_bytes());
+        data[optional + 60..optional + 64].copy_from_slice(&0x200u32.to_le_bytes());
+
+        // Section contents go after the headers, each one padded like a real file
+        for (i, (name, address, contents)) in sections.iter().enumerate() {
+            let raw_offset = data.len();
+            let raw_size = contents.len().next_multiple_of(0x200);
+            data.extend_from_slice(contents);
+            data.resize(raw_offset + raw_size, 0);
+
+            let header = optional + optional_size + i * 40;
+            data[header..header + name.len()].copy_from_slice(name.as_bytes());
+            data[header + 8..header + 12].copy_from_slice(&(contents.len() as u32).to_le_bytes());
+            data[header + 12..header + 16].copy_from_slice(&(*address as u32).to_le_bytes());
+            data[header + 16..header + 20].copy_from_slice(&(raw_size as u32).to_le_bytes());
+            data[header + 20..header + 24].copy_from_slice(&(raw_offset as u32).to_le_bytes());
+        }
+
+        return data;
+    }
+
+    #[test]
+    fn sections_are_mapped_to_their_address() {
+        let data = build_pe(
+            true,
+            0x140000000,
+            &[
+                (".text", 0x1000, vec![0x90, 0xc3]),
+                (".data", 0x3000, vec![0x11, 0x22, 0x33, 0x44]),
+            ],
+        );
+        let pe_image = PeImage::parse(&data).unwrap();
+
+        assert!(pe_image.is_64_bit);
+        assert_eq!(pe_image.image_base, 0x140000000);
+        assert_eq!(pe_image.image.len(), 0x4000);
+        assert_eq!(&pe_image.image[0x1000..0x1002], &[0x90, 0xc3]);
+        assert_eq!(pe_image.read_u32(0x3000), Some(0x44332211));
+        assert_eq!(pe_image.section_at(0x3002).unwrap().name, ".data");
+        assert!(pe_image.section_at(0x2000).is_none());
+    }
+
+    #[test]
+    fn scan_uses_wildcards() {
+        let data = build_pe(
+            false,
+            0x400000,
+            &[(".text", 0x1000, vec![0xa1, 0x10, 0x20, 0xa1, 0x30, 0x20])],
+        );
+        let pe_image = PeImage::parse(&data).unwrap();
+
+        assert!(!pe_image.is_64_bit);
+        assert_eq!(pe_image.image_base, 0x400000);
+        assert_eq!(
+            pe_image.scan(&string_to_pattern("a1 ? 20").unwrap()),
+            vec![0x1000, 0x1003]
+        );
+        assert!(string_to_pattern("a1 zz").is_err());
+    }
+
+    #[test]
+    fn broken_headers_are_errors() {
+        assert!(PeImage::parse(b"not an executable").is_err());
+
+        let mut data = build_pe(true, 0x140000000, &[]);
+        data[0x40] = b'X';
+        assert!(PeImage::parse(&data).is_err());
+    }
+}